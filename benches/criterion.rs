@@ -13,7 +13,7 @@ macro_rules! gen_benchmarks {
 
                     c.bench_function(&format!("Day {} silver (sample)", stringify!($day_index)), |b| {
                         use [<day $day_index>]::Day;
-                        b.iter(|| Day::calculate_silver(black_box(Day::INPUT_SAMPLE)))
+                        b.iter(|| Day::calculate_silver_sample(black_box(Day::INPUT_SAMPLE)))
                     });
                     c.bench_function(&format!("Day {} silver (real)", stringify!($day_index)), |b| {
                         use [<day $day_index>]::Day;
@@ -21,7 +21,7 @@ macro_rules! gen_benchmarks {
                     });
                     c.bench_function(&format!("Day {} gold (sample)", stringify!($day_index)), |b| {
                         use [<day $day_index>]::Day;
-                        b.iter(|| Day::calculate_gold(black_box(Day::INPUT_SAMPLE_GOLD)))
+                        b.iter(|| Day::calculate_gold_sample(black_box(Day::INPUT_SAMPLE_GOLD)))
                     });
                     c.bench_function(&format!("Day {} gold (real)", stringify!($day_index)), |b| {
                         use [<day $day_index>]::Day;
@@ -43,6 +43,7 @@ macro_rules! gen_benchmarks {
         paste::paste!{
             criterion_main! {
                 $([<day_ $day_index>],)*
+                extra,
             }
         }
     }
@@ -51,3 +52,150 @@ macro_rules! gen_benchmarks {
 gen_benchmarks! {
     01, 02, 03, 04, 05, 06, 07, 08, 09, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
 }
+
+/// Benchmarks that don't fit the sample/real input pattern, such as ones on generated inputs.
+fn extra_benches(c: &mut criterion::Criterion) {
+    #[cfg(feature = "day09")]
+    day_09_extra_benches(c);
+    #[cfg(feature = "day18")]
+    day_18_extra_benches(c);
+    #[cfg(feature = "day19")]
+    day_19_extra_benches(c);
+    #[cfg(feature = "day22")]
+    day_22_extra_benches(c);
+    #[cfg(feature = "day24")]
+    day_24_extra_benches(c);
+
+    _ = c;
+}
+
+#[cfg(feature = "day09")]
+fn day_09_extra_benches(c: &mut criterion::Criterion) {
+    use aoc2024::{day09::*, SolutionGold, SolutionSilver};
+    use criterion::{black_box, BenchmarkId};
+
+    let mut group = c.benchmark_group("Day 09 generated disk map");
+    group.sample_size(10);
+    for len in [10_000, 20_000, 100_000, 1_000_000] {
+        let input = generate_disk_map(len, 9);
+        group.bench_with_input(BenchmarkId::new("silver", len), &input, |b, input| {
+            b.iter(|| Day::calculate_silver(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("gold", len), &input, |b, input| {
+            b.iter(|| Day::calculate_gold(black_box(input)))
+        });
+
+        // the reference implementation is quadratic, so only run it on sizes close to the real input
+        if len <= 20_000 {
+            group.bench_with_input(
+                BenchmarkId::new("silver (reference)", len),
+                &input,
+                |b, input| b.iter(|| reference::calculate_silver(black_box(input))),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("gold (reference)", len),
+                &input,
+                |b, input| b.iter(|| reference::calculate_gold(black_box(input))),
+            );
+        }
+    }
+    group.finish();
+}
+
+#[cfg(feature = "day18")]
+fn day_18_extra_benches(c: &mut criterion::Criterion) {
+    use aoc2024::{day18::*, SolutionSilver};
+    use criterion::black_box;
+
+    let mut group = c.benchmark_group("Day 18 first blocking byte");
+    group.sample_size(10);
+    group.bench_function("reference", |b| {
+        b.iter(|| reference::calculate_gold(black_box(Day::INPUT_REAL)))
+    });
+    group.bench_function("binary search", |b| {
+        b.iter(|| first_blocking_binary_search(&parse(black_box(Day::INPUT_REAL)), 71))
+    });
+    group.bench_function("union-find", |b| {
+        b.iter(|| first_blocking_union_find(&parse(black_box(Day::INPUT_REAL)), 71))
+    });
+    group.finish();
+}
+
+#[cfg(feature = "day19")]
+fn day_19_extra_benches(c: &mut criterion::Criterion) {
+    use aoc2024::{day19::*, SolutionGold, SolutionSilver};
+    use criterion::{black_box, BenchmarkId};
+
+    let mut group = c.benchmark_group("Day 19 towel arrangements");
+    group.sample_size(10);
+    let inputs = [
+        ("real", Day::INPUT_REAL.to_string()),
+        ("generated", generate_towels(3000, 400, 19)),
+    ];
+    for (name, input) in &inputs {
+        group.bench_with_input(BenchmarkId::new("trie", name), input, |b, input| {
+            b.iter(|| Day::calculate_gold(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("recursive", name), input, |b, input| {
+            b.iter(|| reference::calculate_gold(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "day22")]
+fn day_22_extra_benches(c: &mut criterion::Criterion) {
+    use aoc2024::{day22::*, SolutionSilver};
+    use criterion::black_box;
+
+    let seeds = Day::INPUT_REAL
+        .lines()
+        .map(|line| line.parse::<u32>().unwrap())
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("Day 22 secret after 2000 steps");
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            black_box(&seeds)
+                .iter()
+                .map(|&seed| SecretRng::new(seed).take(2000).last().unwrap() as usize)
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("jump-ahead", |b| {
+        b.iter(|| {
+            black_box(&seeds)
+                .iter()
+                .map(|&seed| SecretRng::new(seed).nth(1999).unwrap() as usize)
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            let mut secrets = black_box(&seeds).clone();
+            evolve_batch(&mut secrets, 2000);
+            secrets.into_iter().map(|s| s as usize).sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+#[cfg(feature = "day24")]
+fn day_24_extra_benches(c: &mut criterion::Criterion) {
+    use aoc2024::day24::{generate_adder, Circuit};
+    use criterion::{black_box, BenchmarkId};
+
+    let mut group = c.benchmark_group("Day 24 generated adder");
+    for bits in [45, 500, 5000] {
+        let input = generate_adder(bits, 24);
+        group.bench_with_input(BenchmarkId::new("evaluate", bits), &input, |b, input| {
+            b.iter(|| Circuit::parse(black_box(input)).evaluate())
+        });
+        group.bench_with_input(BenchmarkId::new("verify", bits), &input, |b, input| {
+            b.iter(|| Circuit::parse(black_box(input)).verify_adder())
+        });
+    }
+    group.finish();
+}
+
+criterion::criterion_group!(extra, extra_benches);
@@ -1,5 +1,9 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
 use super::*;
 
+pub mod reference;
+
 pub struct Day;
 
 impl SolutionSilver<usize> for Day {
@@ -8,161 +12,315 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let input = input.as_bytes();
-
-        let mut disk = Vec::with_capacity(input.len());
-        let mut is_file = true;
-        let mut last_file_id = 0usize;
-        for file in input {
-            let file_len = (file - b'0') as usize;
-            if is_file {
-                disk.push((Some(last_file_id), file_len));
-                last_file_id += 1;
-            } else {
-                disk.push((None, file_len));
-            }
+        let disk = parse(input.as_bytes());
+        checksum(&compact_blocks(&disk)).try_into().unwrap()
+    }
+}
+
+impl SolutionGold<usize, usize> for Day {
+    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
-            is_file = !is_file;
+    fn calculate_gold(input: &str) -> usize {
+        let disk = parse(input.as_bytes());
+        checksum(&compact_files(&disk)).try_into().unwrap()
+    }
+}
+
+/// A span of blocks on the disk: the id of the file it holds, or `None` for free space, and its
+/// length.
+pub type Span = (Option<usize>, usize);
+
+pub fn parse(input: &[u8]) -> Vec<Span> {
+    let mut disk = Vec::with_capacity(input.len());
+    let mut is_file = true;
+    let mut last_file_id = 0usize;
+    for file in input {
+        let file_len = (file - b'0') as usize;
+        if is_file {
+            disk.push((Some(last_file_id), file_len));
+            last_file_id += 1;
+        } else {
+            disk.push((None, file_len));
         }
 
-        // compact
-        let mut i_src = disk.len() - 1;
-        'outer: loop {
-            if disk[i_src].0.is_none() {
-                i_src -= 1;
-                continue;
+        is_file = !is_file;
+    }
+
+    disk
+}
+
+/// Moves file blocks one at a time from the end of the disk to the leftmost free block.
+///
+/// A left pointer walks over the disk, filling every free span it finds with blocks taken from the
+/// file under the right pointer, until both meet.
+pub fn compact_blocks(disk: &[Span]) -> Vec<Span> {
+    let mut compacted = Vec::with_capacity(disk.len());
+    let disk_len = disk.iter().map(|&(_, len)| len).sum::<usize>();
+
+    let mut left = 0;
+    let mut right = disk.len();
+    let mut right_remaining = 0;
+    // moves the right pointer to the next file that is still right of the left pointer
+    let next_file = |right: &mut usize, right_remaining: &mut usize, left: usize| {
+        *right_remaining = 0;
+        while *right > left + 1 {
+            *right -= 1;
+            if disk[*right].0.is_some() {
+                *right_remaining = disk[*right].1;
+                return;
             }
+        }
+        *right = left;
+    };
+    next_file(&mut right, &mut right_remaining, left);
 
-            let mut i_dst = 0;
-            loop {
-                // skip file blocks and empty blocks
-                if disk[i_dst].0.is_some() || disk[i_dst].1 == 0 {
-                    i_dst += 1;
-                    continue;
-                }
+    while left < right {
+        match disk[left] {
+            (Some(file_id), file_len) => compacted.push((Some(file_id), file_len)),
+            (None, mut empty_len) => {
+                while empty_len > 0 && left < right {
+                    let to_move = empty_len.min(right_remaining);
+                    if to_move > 0 {
+                        compacted.push((disk[right].0, to_move));
+                    }
+                    empty_len -= to_move;
+                    right_remaining -= to_move;
 
-                // move what we can
-                let to_move = disk[i_dst].1.min(disk[i_src].1);
-                let left_over = disk[i_src].1 - to_move;
-                let val = disk[i_src].0;
-                if to_move == 0 {
-                    break 'outer; // weird edge condition for the end of the compacted disk
+                    if right_remaining == 0 {
+                        next_file(&mut right, &mut right_remaining, left);
+                    }
                 }
-                debug_assert_ne!(to_move, 0);
-                debug_assert!(val.is_some());
+            }
+        }
 
-                disk[i_src].1 -= to_move;
-                disk[i_dst].1 -= to_move;
-                debug_assert_eq!(disk[i_src].1, left_over);
+        left += 1;
+    }
 
-                // insert before dest
-                disk.insert(i_dst, (val, to_move)); // TODO: slow bc moves everything after it
-                i_src += 1;
+    // the file the pointers met at may have only been partially moved
+    if left == right && right_remaining > 0 {
+        compacted.push((disk[right].0, right_remaining));
+    }
 
-                if disk[i_src].1 == 0 {
-                    disk[i_src].0 = None;
-                    i_src -= 1;
-                    break;
-                }
+    let used_len = compacted.iter().map(|&(_, len)| len).sum::<usize>();
+    if used_len < disk_len {
+        compacted.push((None, disk_len - used_len));
+    }
 
-                i_dst += 1;
-                if i_dst >= i_src {
-                    break;
-                }
-            }
+    compacted
+}
 
-            i_src -= 1;
-            if i_src == 0 {
-                break;
+/// Moves whole files, starting at the highest id, to the leftmost free span they fit in.
+///
+/// Free spans are kept in a min-heap of their positions per span length, so finding the leftmost
+/// span that fits only needs to look at the top of 9 heaps.
+pub fn compact_files(disk: &[Span]) -> Vec<Span> {
+    let mut free_spans: [BinaryHeap<Reverse<usize>>; 10] = Default::default();
+    let mut files = Vec::with_capacity(disk.len() / 2 + 1);
+
+    let mut disk_len = 0;
+    for &(file_id, len) in disk {
+        match file_id {
+            Some(file_id) => files.push((disk_len, file_id, len)),
+            None if len > 0 => free_spans[len].push(Reverse(disk_len)),
+            None => {}
+        }
+        disk_len += len;
+    }
+
+    // files only ever move to the left, so the space they leave behind is never used again
+    for (file_pos, _, file_len) in files.iter_mut().rev() {
+        let target = (*file_len..free_spans.len())
+            .filter_map(|len| free_spans[len].peek().map(|&Reverse(pos)| (pos, len)))
+            .filter(|&(pos, _)| pos < *file_pos)
+            .min();
+
+        if let Some((empty_pos, empty_len)) = target {
+            free_spans[empty_len].pop();
+            if empty_len > *file_len {
+                free_spans[empty_len - *file_len].push(Reverse(empty_pos + *file_len));
             }
+            *file_pos = empty_pos;
         }
+    }
 
-        disk.into_iter()
-            .fold((0, 0), |(acc_idx, acc_checksum), (file_val, file_len)| {
-                if let Some(file_val) = file_val {
-                    (
-                        acc_idx + file_len,
-                        acc_checksum
-                            + match file_len {
-                                0 => 0,
-                                _ => {
-                                    let range_center = acc_idx as f32 + (file_len - 1) as f32 * 0.5;
-                                    let range_indices_sum =
-                                        (range_center * file_len as f32) as usize;
-                                    range_indices_sum * file_val
-                                }
-                            },
-                    )
-                } else {
-                    (acc_idx + file_len, acc_checksum)
-                }
-            })
-            .1
+    files.sort_unstable();
+
+    let mut compacted = Vec::with_capacity(disk.len());
+    let mut cursor = 0;
+    for (file_pos, file_id, file_len) in files {
+        if file_pos > cursor {
+            compacted.push((None, file_pos - cursor));
+        }
+        compacted.push((Some(file_id), file_len));
+        cursor = file_pos + file_len;
+    }
+    if disk_len > cursor {
+        compacted.push((None, disk_len - cursor));
     }
+
+    compacted
 }
 
-impl SolutionGold<usize, usize> for Day {
-    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
+/// Sums the position of every block multiplied by the id of the file it holds.
+///
+/// The positions covered by a span form an arithmetic series, so each span is summed in one go.
+/// This is done in a `u128` because large disks overflow a `u64`.
+fn checksum(disk: &[Span]) -> u128 {
+    disk.iter()
+        .fold(
+            (0u128, 0u128),
+            |(acc_idx, acc_checksum), &(file_id, file_len)| {
+                let file_len = file_len as u128;
+                let checksum = match file_id {
+                    // acc_idx + (acc_idx + 1) + ... + (acc_idx + file_len - 1)
+                    Some(file_id) => {
+                        let range_indices_sum =
+                            acc_idx * file_len + file_len * file_len.saturating_sub(1) / 2;
+                        range_indices_sum * file_id as u128
+                    }
+                    None => 0,
+                };
+                (acc_idx + file_len, acc_checksum + checksum)
+            },
+        )
+        .1
+}
 
-    fn calculate_gold(input: &str) -> usize {
-        let input = input.as_bytes();
-
-        let mut disk = Vec::with_capacity(input.len());
-        let mut is_file = true;
-        let mut last_file_id = 0usize;
-        for file in input {
-            let file_len = (file - b'0') as usize;
-            if is_file {
-                disk.push((Some(last_file_id), file_len));
-                last_file_id += 1;
-            } else {
-                disk.push((None, file_len));
+/// Renders the disk the way the puzzle does, with `.` for free blocks. File ids above 9 are shown
+/// by their last digit.
+pub fn render(disk: &[Span]) -> String {
+    render_blocks(
+        disk.iter()
+            .flat_map(|&(file_id, len)| std::iter::repeat_n(file_id, len)),
+    )
+}
+
+fn render_blocks(blocks: impl Iterator<Item = Option<usize>>) -> String {
+    blocks
+        .map(|file_id| match file_id {
+            Some(file_id) => (b'0' + (file_id % 10) as u8) as char,
+            None => '.',
+        })
+        .collect()
+}
+
+/// A single step of a compaction: `len` blocks of a file moved from block `from` to block `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub file_id: usize,
+    pub from: usize,
+    pub to: usize,
+    pub len: usize,
+}
+
+/// Replays a compaction one move at a time, keeping the state of the disk in between so it can be
+/// rendered. This works block by block, so it is only meant for debugging.
+pub struct Replay {
+    blocks: Vec<Option<usize>>,
+    strategy: ReplayStrategy,
+}
+
+enum ReplayStrategy {
+    /// Moves single blocks, like [`compact_blocks`].
+    Blocks,
+    /// Moves whole files, like [`compact_files`]. Holds the position and length of every file not
+    /// yet considered for moving.
+    Files(Vec<(usize, usize)>),
+}
+
+impl Replay {
+    pub fn blocks(disk: &[Span]) -> Self {
+        Self {
+            blocks: Self::expand(disk),
+            strategy: ReplayStrategy::Blocks,
+        }
+    }
+
+    pub fn files(disk: &[Span]) -> Self {
+        let mut files = vec![];
+        let mut pos = 0;
+        for &(file_id, len) in disk {
+            if file_id.is_some() {
+                files.push((pos, len));
             }
+            pos += len;
+        }
 
-            is_file = !is_file;
+        Self {
+            blocks: Self::expand(disk),
+            strategy: ReplayStrategy::Files(files),
         }
+    }
 
-        // compact
-        for i in (0..disk.len()).rev() {
-            if let (Some(file_id), file_len) = disk[i] {
-                for j in 0..i {
-                    if let (None, empty_len) = disk[j] {
-                        if empty_len >= file_len {
-                            disk[i] = (None, file_len);
-
-                            disk.insert(j, (Some(file_id), file_len)); // TODO: slow bc moves everything after it
-                            disk[j + 1].1 -= file_len;
-                            break;
-                        }
-                    }
+    fn expand(disk: &[Span]) -> Vec<Option<usize>> {
+        disk.iter()
+            .flat_map(|&(file_id, len)| std::iter::repeat_n(file_id, len))
+            .collect()
+    }
+
+    /// Renders the current state of the disk, see [`render`].
+    pub fn render(&self) -> String {
+        render_blocks(self.blocks.iter().copied())
+    }
+}
+
+impl Iterator for Replay {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        match &mut self.strategy {
+            ReplayStrategy::Blocks => {
+                let to = self.blocks.iter().position(Option::is_none)?;
+                let from = self.blocks.iter().rposition(Option::is_some)?;
+                if to > from {
+                    return None;
                 }
+
+                let file_id = self.blocks[from].unwrap();
+                self.blocks.swap(from, to);
+                Some(Move {
+                    file_id,
+                    from,
+                    to,
+                    len: 1,
+                })
             }
-        }
+            ReplayStrategy::Files(files) => {
+                // files that don't fit anywhere don't move, so they don't produce a step
+                while let Some((from, len)) = files.pop() {
+                    let file_id = files.len();
+                    let target = (0..from)
+                        .find(|&pos| self.blocks[pos..pos + len].iter().all(Option::is_none));
 
-        disk.into_iter()
-            .fold((0, 0), |(acc_idx, acc_checksum), (file_val, file_len)| {
-                if let Some(file_val) = file_val {
-                    (
-                        acc_idx + file_len,
-                        acc_checksum
-                            + match file_len {
-                                0 => 0,
-                                _ => {
-                                    let range_center = acc_idx as f32 + (file_len - 1) as f32 * 0.5;
-                                    let range_indices_sum =
-                                        (range_center * file_len as f32) as usize;
-                                    range_indices_sum * file_val
-                                }
-                            },
-                    )
-                } else {
-                    (acc_idx + file_len, acc_checksum)
+                    if let Some(to) = target {
+                        self.blocks[to..to + len].fill(Some(file_id));
+                        self.blocks[from..from + len].fill(None);
+                        return Some(Move {
+                            file_id,
+                            from,
+                            to,
+                            len,
+                        });
+                    }
                 }
-            })
-            .1
+
+                None
+            }
+        }
     }
 }
 
+/// Generates a random disk map of the given length, with files of 1 to 9 blocks.
+pub fn generate_disk_map(len: usize, seed: u64) -> String {
+    let mut rng = utils::XorShift::new(seed);
+    (0..len)
+        .map(|i| match i % 2 {
+            0 => (b'1' + rng.next_below(9) as u8) as char,
+            _ => (b'0' + rng.next_below(10) as u8) as char,
+        })
+        .collect()
+}
+
 #[test]
 fn test_silver_sample() {
     let output = Day::calculate_silver(Day::INPUT_SAMPLE);
@@ -186,3 +344,175 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(6408966547049, output);
 }
+
+#[test]
+fn test_against_brute_force() {
+    let block_checksum = |blocks: &[Option<usize>]| {
+        (blocks.iter().enumerate())
+            .filter_map(|(i, file_id)| file_id.map(|file_id| i * file_id))
+            .sum::<usize>()
+    };
+
+    for seed in 1..20 {
+        let input = generate_disk_map(1 + seed as usize * 37, seed);
+        let disk = parse(input.as_bytes());
+
+        // silver: swap the leftmost free block with the rightmost file block
+        let mut blocks = Replay::expand(&disk);
+        let (mut left, mut right) = (0, blocks.len() - 1);
+        loop {
+            while left < blocks.len() && blocks[left].is_some() {
+                left += 1;
+            }
+            while right > 0 && blocks[right].is_none() {
+                right -= 1;
+            }
+            if left >= right {
+                break;
+            }
+            blocks.swap(left, right);
+        }
+        assert_eq!(blocks, Replay::expand(&compact_blocks(&disk)), "{input}");
+        assert_eq!(block_checksum(&blocks), Day::calculate_silver(&input));
+
+        // gold: move each file to the leftmost run of free blocks it fits in
+        let mut blocks = Replay::expand(&disk);
+        let file_count = disk.len().div_ceil(2);
+        for file_id in (0..file_count).rev() {
+            let file_pos = blocks.iter().position(|&b| b == Some(file_id)).unwrap();
+            let file_len = disk[file_id * 2].1;
+            let target = (0..file_pos).find(|&pos| {
+                pos + file_len <= file_pos
+                    && blocks[pos..pos + file_len].iter().all(Option::is_none)
+            });
+            if let Some(target) = target {
+                blocks[target..target + file_len].fill(Some(file_id));
+                blocks[file_pos..file_pos + file_len].fill(None);
+            }
+        }
+        assert_eq!(blocks, Replay::expand(&compact_files(&disk)), "{input}");
+        assert_eq!(block_checksum(&blocks), Day::calculate_gold(&input));
+        assert_eq!(
+            reference::calculate_gold(&input),
+            Day::calculate_gold(&input),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_checksum_large() {
+    // sums block by block, without keeping all blocks in memory
+    let block_checksum = |disk: &[Span]| {
+        let mut pos = 0u128;
+        let mut sum = 0u128;
+        for &(file_id, len) in disk {
+            for _ in 0..len {
+                sum += pos * file_id.map_or(0, |id| id as u128);
+                pos += 1;
+            }
+        }
+        sum
+    };
+
+    // block positions past 2^24 can't be represented exactly in an `f32`
+    let input = generate_disk_map(4_000_000, 35);
+    let disk = parse(input.as_bytes());
+    assert!(disk.iter().map(|&(_, len)| len).sum::<usize>() > 1 << 24);
+
+    for compacted in [compact_blocks(&disk), compact_files(&disk)] {
+        assert_eq!(block_checksum(&compacted), checksum(&compacted));
+    }
+}
+
+#[test]
+fn test_render() {
+    let disk = parse(b"12345");
+    assert_eq!("0..111....22222", render(&disk));
+    assert_eq!("022111222......", render(&compact_blocks(&disk)));
+
+    let disk = parse(Day::INPUT_SAMPLE.as_bytes());
+    assert_eq!("00...111...2...333.44.5555.6666.777.888899", render(&disk));
+    assert_eq!(
+        "0099811188827773336446555566..............",
+        render(&compact_blocks(&disk))
+    );
+    assert_eq!(
+        "00992111777.44.333....5555.6666.....8888..",
+        render(&compact_files(&disk))
+    );
+}
+
+#[test]
+fn test_replay_blocks() {
+    let disk = parse(Day::INPUT_SAMPLE.as_bytes());
+    let mut replay = Replay::blocks(&disk);
+
+    let mut states = vec![replay.render()];
+    while replay.next().is_some() {
+        states.push(replay.render());
+    }
+
+    assert_eq!(
+        vec![
+            "00...111...2...333.44.5555.6666.777.888899",
+            "009..111...2...333.44.5555.6666.777.88889.",
+            "0099.111...2...333.44.5555.6666.777.8888..",
+            "00998111...2...333.44.5555.6666.777.888...",
+            "009981118..2...333.44.5555.6666.777.88....",
+            "0099811188.2...333.44.5555.6666.777.8.....",
+            "009981118882...333.44.5555.6666.777.......",
+            "0099811188827..333.44.5555.6666.77........",
+            "00998111888277.333.44.5555.6666.7.........",
+            "009981118882777333.44.5555.6666...........",
+            "009981118882777333644.5555.666............",
+            "00998111888277733364465555.66.............",
+            "0099811188827773336446555566..............",
+        ],
+        states
+    );
+
+    let mut replay = Replay::blocks(&disk);
+    assert_eq!(
+        Some(Move {
+            file_id: 9,
+            from: 41,
+            to: 2,
+            len: 1
+        }),
+        replay.next()
+    );
+}
+
+#[test]
+fn test_replay_files() {
+    let disk = parse(Day::INPUT_SAMPLE.as_bytes());
+    let mut replay = Replay::files(&disk);
+
+    let mut states = vec![replay.render()];
+    while replay.next().is_some() {
+        states.push(replay.render());
+    }
+
+    assert_eq!(
+        vec![
+            "00...111...2...333.44.5555.6666.777.888899",
+            "0099.111...2...333.44.5555.6666.777.8888..",
+            "0099.1117772...333.44.5555.6666.....8888..",
+            "0099.111777244.333....5555.6666.....8888..",
+            "00992111777.44.333....5555.6666.....8888..",
+        ],
+        states
+    );
+
+    let moves = Replay::files(&disk).collect::<Vec<_>>();
+    assert_eq!(
+        Move {
+            file_id: 7,
+            from: 32,
+            to: 8,
+            len: 3
+        },
+        moves[1]
+    );
+}
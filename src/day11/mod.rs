@@ -61,7 +61,7 @@ fn resolve_number<const N: usize>(
     }
 
     let digit_count = num.ilog10() + 1;
-    if digit_count % 2 != 0 {
+    if !digit_count.is_multiple_of(2) {
         let res = resolve_number(num * 2024, depth - 1, memoize);
         memoize.entry(num).or_insert([0; N])[N - depth as usize] = res;
         return res;
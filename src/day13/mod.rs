@@ -8,46 +8,11 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        input
-            .split("\n\n")
-            .map(|conf| {
-                let mut lines = conf.lines();
-                let line_1 = lines.next().unwrap().split_once(": ").unwrap().1;
-                let line_2 = lines.next().unwrap().split_once(": ").unwrap().1;
-                let line_3 = lines.next().unwrap().split_once(": ").unwrap().1;
-
-                let (a_x, a_y) = line_1.split_once(", ").unwrap();
-                let (a_x, a_y) = (
-                    a_x[2..].parse::<usize>().unwrap(),
-                    a_y[2..].parse::<usize>().unwrap(),
-                );
-                let (b_x, b_y) = line_2.split_once(", ").unwrap();
-                let (b_x, b_y) = (
-                    b_x[2..].parse::<usize>().unwrap(),
-                    b_y[2..].parse::<usize>().unwrap(),
-                );
-                let (p_x, p_y) = line_3.split_once(", ").unwrap();
-                let (p_x, p_y) = (
-                    p_x[2..].parse::<usize>().unwrap(),
-                    p_y[2..].parse::<usize>().unwrap(),
-                );
-
-                let only_a = (p_x / a_x).min(p_y / a_y);
-
-                for pushes_a in 0..=only_a {
-                    let (target_x, target_y) = (p_x - pushes_a * a_x, p_y - pushes_a * a_y);
-
-                    let pushes_b = (target_x / b_x).min(target_y / b_y);
-
-                    if target_x == pushes_b * b_x && target_y == pushes_b * b_y {
-                        debug_assert_eq!(target_x / b_x, target_y / b_y);
-                        return pushes_a * 3 + pushes_b;
-                    }
-                }
-
-                0
-            })
-            .sum()
+        explain(input, 0).iter().map(MachineReport::tokens).sum()
+    }
+
+    fn explain_silver(input: &str) -> Option<String> {
+        Some(render_table(&explain(input, 0)))
     }
 }
 
@@ -55,61 +20,222 @@ impl SolutionGold<usize, usize> for Day {
     const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
     fn calculate_gold(input: &str) -> usize {
-        input
-            .split("\n\n")
-            .map(|conf| {
-                let mut lines = conf.lines();
-                let line_1 = lines.next().unwrap().split_once(": ").unwrap().1;
-                let line_2 = lines.next().unwrap().split_once(": ").unwrap().1;
-                let line_3 = lines.next().unwrap().split_once(": ").unwrap().1;
-
-                let (a_x, a_y) = line_1.split_once(", ").unwrap();
-                let (a_x, a_y) = (
-                    a_x[2..].parse::<usize>().unwrap(),
-                    a_y[2..].parse::<usize>().unwrap(),
-                );
-                let (b_x, b_y) = line_2.split_once(", ").unwrap();
-                let (b_x, b_y) = (
-                    b_x[2..].parse::<usize>().unwrap(),
-                    b_y[2..].parse::<usize>().unwrap(),
-                );
-                let (p_x, p_y) = line_3.split_once(", ").unwrap();
-                let (p_x, p_y) = (
-                    p_x[2..].parse::<usize>().unwrap() + 10000000000000,
-                    p_y[2..].parse::<usize>().unwrap() + 10000000000000,
-                );
-
-                let x = (b_y as f64 * p_x as f64 - b_x as f64 * p_y as f64)
-                    / (a_x as f64 * b_y as f64 - b_x as f64 * a_y as f64);
-                let y = (a_x as f64 * p_y as f64 - a_y as f64 * p_x as f64)
-                    / (a_x as f64 * b_y as f64 - b_x as f64 * a_y as f64);
-
-                if x.fract() > 0.0001 || y.fract() > 0.0001 {
-                    0
-                } else {
-                    x as usize * 3 + y as usize
-                }
-            })
+        explain(input, GOLD_PRIZE_OFFSET)
+            .iter()
+            .map(MachineReport::tokens)
             .sum()
     }
+
+    fn explain_gold(input: &str) -> Option<String> {
+        Some(render_table(&explain(input, GOLD_PRIZE_OFFSET)))
+    }
+}
+
+/// How much further away the prizes are in gold, on both axes.
+const GOLD_PRIZE_OFFSET: i64 = 10000000000000;
+
+/// Solves every claw machine in the input, after moving the prizes by `prize_offset` on both axes.
+pub fn explain(input: &str, prize_offset: i64) -> Vec<MachineReport> {
+    parse(input)
+        .map(|machine| Machine {
+            prize: (
+                machine.prize.0 + prize_offset,
+                machine.prize.1 + prize_offset,
+            ),
+            ..machine
+        })
+        .map(|machine| MachineReport {
+            machine,
+            presses: machine.solve(),
+        })
+        .collect()
+}
+
+/// A claw machine together with its solution, if it has one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MachineReport {
+    pub machine: Machine,
+    /// The cheapest amount of presses of A and B that wins the prize.
+    pub presses: Option<(usize, usize)>,
+}
+
+impl MachineReport {
+    pub fn is_solvable(&self) -> bool {
+        self.presses.is_some()
+    }
+
+    /// The tokens needed to win the prize, or 0 if it can't be won.
+    pub fn tokens(&self) -> usize {
+        self.presses.map_or(0, |(a, b)| token_cost(a, b))
+    }
 }
 
-/// https://github.com/TheAlgorithms/Rust/blob/master/src/math/lcm_of_n_numbers.rs
-pub fn lcm(nums: &[usize]) -> usize {
-    if nums.len() == 1 {
-        return nums[0];
+/// Renders a table with a row for every claw machine.
+pub fn render_table(reports: &[MachineReport]) -> String {
+    let header = ["#", "Button A", "Button B", "Prize", "A", "B", "Tokens"].map(String::from);
+    let rows = reports
+        .iter()
+        .enumerate()
+        .map(|(i, report)| {
+            let Machine {
+                button_a,
+                button_b,
+                prize,
+            } = report.machine;
+            let (presses_a, presses_b, tokens) = match report.presses {
+                Some((a, b)) => (a.to_string(), b.to_string(), report.tokens().to_string()),
+                None => ("-".into(), "-".into(), "unsolvable".into()),
+            };
+
+            [
+                (i + 1).to_string(),
+                format!("X+{}, Y+{}", button_a.0, button_a.1),
+                format!("X+{}, Y+{}", button_b.0, button_b.1),
+                format!("X={}, Y={}", prize.0, prize.1),
+                presses_a,
+                presses_b,
+                tokens,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = header.clone().map(|column| column.len());
+    for row in &rows {
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.len());
+        }
     }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    a * b / gcd_of_two_numbers(a, b)
+
+    std::iter::once(header)
+        .chain(rows)
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .enumerate()
+                .map(|(i, (column, width))| match i {
+                    // text columns are left-aligned, numbers right-aligned
+                    1..=3 => format!("{column:<width$}"),
+                    _ => format!("{column:>width$}"),
+                })
+                .collect::<Vec<_>>()
+                .join(" | ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Machine {
+    pub button_a: (i64, i64),
+    pub button_b: (i64, i64),
+    pub prize: (i64, i64),
 }
 
-/// https://github.com/TheAlgorithms/Rust/blob/master/src/math/lcm_of_n_numbers.rs
-fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
+impl Machine {
+    /// Finds the amount of presses of A and B that reach the prize for the fewest tokens, using
+    /// exact integer arithmetic.
+    pub fn solve(&self) -> Option<(usize, usize)> {
+        let (a_x, a_y) = (self.button_a.0 as i128, self.button_a.1 as i128);
+        let (b_x, b_y) = (self.button_b.0 as i128, self.button_b.1 as i128);
+        let (p_x, p_y) = (self.prize.0 as i128, self.prize.1 as i128);
+
+        let det = a_x * b_y - b_x * a_y;
+        if det != 0 {
+            // Cramer's rule: the only solution, which must be in whole, non-negative presses
+            let a = b_y * p_x - b_x * p_y;
+            let b = a_x * p_y - a_y * p_x;
+            if a % det != 0 || b % det != 0 {
+                return None;
+            }
+            let (a, b) = (a / det, b / det);
+            return (a >= 0 && b >= 0).then_some((a as usize, b as usize));
+        }
+
+        // the buttons move along the same line, which the prize has to be on as well
+        if a_x * p_y != a_y * p_x || b_x * p_y != b_y * p_x {
+            return None;
+        }
+
+        // everything is on one line, so solving a single axis is enough
+        let (a_len, b_len, p_len) = if a_x != 0 || b_x != 0 {
+            (a_x, b_x, p_x)
+        } else if p_x == 0 {
+            (a_y, b_y, p_y)
+        } else {
+            return None;
+        };
+        solve_collinear(a_len, b_len, p_len).map(|(a, b)| (a as usize, b as usize))
     }
-    gcd_of_two_numbers(b, a % b)
+}
+
+/// Minimizes `3 * a + b` over all non-negative `a` and `b` where `a * a_len + b * b_len == p_len`.
+fn solve_collinear(a_len: i128, b_len: i128, p_len: i128) -> Option<(i128, i128)> {
+    match (a_len, b_len) {
+        (0, 0) => return (p_len == 0).then_some((0, 0)),
+        // pressing a button that doesn't move the claw is a waste of tokens
+        (0, _) => return (p_len % b_len == 0 && p_len / b_len >= 0).then(|| (0, p_len / b_len)),
+        (_, 0) => return (p_len % a_len == 0 && p_len / a_len >= 0).then(|| (p_len / a_len, 0)),
+        _ => {}
+    }
+
+    let (gcd, x, y) = math::extended_gcd(a_len, b_len);
+    if p_len % gcd != 0 {
+        return None;
+    }
+
+    // every solution is (a0 + k * step_a, b0 - k * step_b) for some integer k
+    let (a0, b0) = (x * (p_len / gcd), y * (p_len / gcd));
+    let (step_a, step_b) = (b_len / gcd, a_len / gcd);
+
+    // find the range of k for which both press counts are non-negative
+    let mut lowest = None::<i128>;
+    let mut highest = None::<i128>;
+    let mut add_bound = |start: i128, step: i128| {
+        // start + k * step >= 0
+        if step > 0 {
+            let bound = math::div_ceil(-start, step);
+            lowest = Some(lowest.map_or(bound, |l| l.max(bound)));
+        } else {
+            let bound = math::div_floor(-start, step);
+            highest = Some(highest.map_or(bound, |h| h.min(bound)));
+        }
+    };
+    add_bound(a0, step_a);
+    add_bound(b0, -step_b);
+
+    // the cost changes linearly with k, so the cheapest solution is at one end of the range
+    let cost_slope = 3 * step_a - step_b;
+    let k = match (lowest, highest) {
+        (Some(lowest), Some(highest)) if lowest > highest => return None,
+        (Some(lowest), _) if cost_slope >= 0 => lowest,
+        (_, Some(highest)) if cost_slope <= 0 => highest,
+        _ => unreachable!("press counts are non-negative, so the cost is bounded"),
+    };
+
+    Some((a0 + k * step_a, b0 - k * step_b))
+}
+
+pub fn token_cost(presses_a: usize, presses_b: usize) -> usize {
+    presses_a * 3 + presses_b
+}
+
+/// Parses all claw machines in the input.
+pub fn parse(input: &str) -> impl Iterator<Item = Machine> + '_ {
+    input.split("\n\n").map(|conf| {
+        let mut lines = conf.lines().map(|line| {
+            let (x, y) = line.split_once(": ").unwrap().1.split_once(", ").unwrap();
+            (
+                x[2..].parse::<i64>().unwrap(),
+                y[2..].parse::<i64>().unwrap(),
+            )
+        });
+
+        Machine {
+            button_a: lines.next().unwrap(),
+            button_b: lines.next().unwrap(),
+            prize: lines.next().unwrap(),
+        }
+    })
 }
 
 #[test]
@@ -135,3 +261,104 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(83197086729371, output);
 }
+
+#[test]
+fn test_solve_degenerate() {
+    let machine = |button_a, button_b, prize| Machine {
+        button_a,
+        button_b,
+        prize,
+    };
+
+    // B is cheaper per unit of distance
+    assert_eq!(Some((0, 10)), machine((2, 2), (1, 1), (10, 10)).solve());
+    // A is cheaper per unit of distance, but B is needed to make up the difference
+    assert_eq!(Some((3, 1)), machine((4, 4), (1, 1), (13, 13)).solve());
+    assert_eq!(Some((0, 4)), machine((6, 3), (4, 2), (16, 8)).solve());
+    // the prize isn't on the line
+    assert_eq!(None, machine((2, 2), (1, 1), (10, 11)).solve());
+    // not reachable in whole presses
+    assert_eq!(None, machine((4, 4), (6, 6), (9, 9)).solve());
+    assert_eq!(None, machine((4, 0), (6, 0), (9, 0)).solve());
+    // moving only along one axis
+    assert_eq!(Some((0, 3)), machine((0, 5), (0, 2), (0, 6)).solve());
+    // buttons that don't move
+    assert_eq!(Some((0, 0)), machine((0, 0), (0, 0), (0, 0)).solve());
+    assert_eq!(None, machine((0, 0), (0, 0), (1, 0)).solve());
+    assert_eq!(Some((0, 4)), machine((0, 0), (2, 3), (8, 12)).solve());
+    // the solution would need negative presses
+    assert_eq!(None, machine((1, 0), (0, 1), (-1, 5)).solve());
+}
+
+#[test]
+fn test_solve_against_brute_force() {
+    let mut rng = utils::XorShift::new(13);
+    for _ in 0..2000 {
+        let mut next = |max: u64| rng.next_below(max) as i64;
+        let button_a = (next(8), next(8));
+        // make collinear buttons likely
+        let button_b = match next(3) {
+            0 => (button_a.0 * next(4), button_a.1 * next(4)),
+            _ => (next(8), next(8)),
+        };
+        let prize = (next(60), next(60));
+        let machine = Machine {
+            button_a,
+            button_b,
+            prize,
+        };
+
+        let expected = (0..=60)
+            .flat_map(|a| (0..=60).map(move |b| (a, b)))
+            .filter(|&(a, b)| {
+                a as i64 * button_a.0 + b as i64 * button_b.0 == prize.0
+                    && a as i64 * button_a.1 + b as i64 * button_b.1 == prize.1
+            })
+            .min_by_key(|&(a, b)| (token_cost(a, b), a));
+        let expected_cost = expected.map(|(a, b)| token_cost(a, b));
+
+        assert_eq!(
+            expected_cost,
+            machine.solve().map(|(a, b)| token_cost(a, b)),
+            "{machine:?}"
+        );
+    }
+}
+
+#[test]
+fn test_explain() {
+    let reports = explain(Day::INPUT_SAMPLE, 0);
+    assert_eq!(4, reports.len());
+
+    assert_eq!((94, 34), reports[0].machine.button_a);
+    assert_eq!((22, 67), reports[0].machine.button_b);
+    assert_eq!((8400, 5400), reports[0].machine.prize);
+    assert_eq!(Some((80, 40)), reports[0].presses);
+    assert_eq!(280, reports[0].tokens());
+
+    assert!(!reports[1].is_solvable());
+    assert_eq!(0, reports[1].tokens());
+    assert_eq!(Some((38, 86)), reports[2].presses);
+    assert_eq!(200, reports[2].tokens());
+    assert!(!reports[3].is_solvable());
+
+    let reports = explain(Day::INPUT_SAMPLE, GOLD_PRIZE_OFFSET);
+    let solvable = reports
+        .iter()
+        .map(MachineReport::is_solvable)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![false, true, false, true], solvable);
+}
+
+#[test]
+fn test_render_table() {
+    assert_eq!(
+        "\
+# | Button A   | Button B   | Prize            |  A |  B |     Tokens
+1 | X+94, Y+34 | X+22, Y+67 | X=8400, Y=5400   | 80 | 40 |        280
+2 | X+26, Y+66 | X+67, Y+21 | X=12748, Y=12176 |  - |  - | unsolvable
+3 | X+17, Y+86 | X+84, Y+37 | X=7870, Y=6450   | 38 | 86 |        200
+4 | X+69, Y+23 | X+27, Y+71 | X=18641, Y=10279 |  - |  - | unsolvable",
+        render_table(&explain(Day::INPUT_SAMPLE, 0))
+    );
+}
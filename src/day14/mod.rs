@@ -8,61 +8,11 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        const WIDTH: isize = 101;
-        const HEIGHT: isize = 103;
-
-        let mut robots = input
-            .lines()
-            .map(|line| {
-                let (p, v) = line.split_once(' ').unwrap();
-                let p = p.split_once('=').unwrap().1.split_once(',').unwrap();
-                let v = v.split_once('=').unwrap().1.split_once(',').unwrap();
-                let p = (p.0.parse::<isize>().unwrap(), p.1.parse::<isize>().unwrap());
-                let v = (v.0.parse::<isize>().unwrap(), v.1.parse::<isize>().unwrap());
-
-                (p, v)
-            })
-            .collect::<Vec<_>>();
-
-        for _ in 0..100 {
-            for r in &mut robots {
-                r.0 = (r.0 .0 + r.1 .0, r.0 .1 + r.1 .1);
-
-                while r.0 .0 < 0 {
-                    r.0 .0 += WIDTH;
-                }
-                while r.0 .1 < 0 {
-                    r.0 .1 += HEIGHT;
-                }
-                while r.0 .0 >= WIDTH {
-                    r.0 .0 -= WIDTH;
-                }
-                while r.0 .1 >= HEIGHT {
-                    r.0 .1 -= HEIGHT;
-                }
-            }
-        }
+        safety_factor(input, Arena::default())
+    }
 
-        const X_MIDDLE: isize = WIDTH / 2;
-        const Y_MIDDLE: isize = HEIGHT / 2;
-        let top_left_quadrant = robots
-            .iter()
-            .filter(|r| r.0 .0 < X_MIDDLE && r.0 .1 < Y_MIDDLE)
-            .count();
-        let top_right_quadrant = robots
-            .iter()
-            .filter(|r| r.0 .0 > X_MIDDLE && r.0 .1 < Y_MIDDLE)
-            .count();
-        let bottom_left_quadrant = robots
-            .iter()
-            .filter(|r| r.0 .0 < X_MIDDLE && r.0 .1 > Y_MIDDLE)
-            .count();
-        let bottom_right_quadrant = robots
-            .iter()
-            .filter(|r| r.0 .0 > X_MIDDLE && r.0 .1 > Y_MIDDLE)
-            .count();
-
-        top_left_quadrant * top_right_quadrant * bottom_left_quadrant * bottom_right_quadrant
+    fn calculate_silver_sample(input: &str) -> usize {
+        safety_factor(input, Arena::SAMPLE)
     }
 }
 
@@ -70,63 +20,133 @@ impl SolutionGold<usize, usize> for Day {
     const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
     fn calculate_gold(input: &str) -> usize {
-        const WIDTH: isize = 101;
-        const HEIGHT: isize = 103;
+        find_picture(input, Arena::default())
+    }
 
-        if input.len() < 1000 {
-            return 0;
-        }
+    fn calculate_gold_sample(input: &str) -> usize {
+        find_picture(input, Arena::SAMPLE)
+    }
+}
 
-        let mut robots = input
-            .lines()
-            .map(|line| {
-                let (p, v) = line.split_once(' ').unwrap();
-                let p = p.split_once('=').unwrap().1.split_once(',').unwrap();
-                let v = v.split_once('=').unwrap().1.split_once(',').unwrap();
-                let p = (p.0.parse::<isize>().unwrap(), p.1.parse::<isize>().unwrap());
-                let v = (v.0.parse::<isize>().unwrap(), v.1.parse::<isize>().unwrap());
-
-                (p, v)
-            })
-            .collect::<Vec<_>>();
-
-        for i in 0..10000 {
-            for r in &mut robots {
-                r.0 = (r.0 .0 + r.1 .0, r.0 .1 + r.1 .1);
-
-                while r.0 .0 < 0 {
-                    r.0 .0 += WIDTH;
-                }
-                while r.0 .1 < 0 {
-                    r.0 .1 += HEIGHT;
-                }
-                while r.0 .0 >= WIDTH {
-                    r.0 .0 -= WIDTH;
-                }
-                while r.0 .1 >= HEIGHT {
-                    r.0 .1 -= HEIGHT;
-                }
-            }
-
-            // check if it looks like a christmas tree?
-
-            // i == 7285
-            // robots.iter().filter(|r| r.0 .0 == 23).count() >= (53 - 21) - 1
-            if robots.iter().filter(|r| r.0 .1 == 21).count() >= 31
-                && robots.iter().filter(|r| r.0 .0 == 23).count() >= (53 - 21)
-            {
-                return i + 1;
-            }
-        }
+/// The dimensions of the space the robots move around in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arena {
+    pub width: isize,
+    pub height: isize,
+}
+
+impl Arena {
+    /// The arena used by the real puzzle input.
+    pub const REAL: Arena = Arena {
+        width: 101,
+        height: 103,
+    };
+    /// The smaller arena used by the example in the puzzle description.
+    pub const SAMPLE: Arena = Arena {
+        width: 11,
+        height: 7,
+    };
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Arena::REAL
+    }
+}
+
+/// Multiplies the number of robots in each quadrant after 100 seconds, ignoring the robots on the
+/// middle row and column.
+pub fn safety_factor(input: &str, arena: Arena) -> usize {
+    let robots = parse(input)
+        .map(|r| r.position_at(100, arena))
+        .collect::<Vec<_>>();
+
+    let x_middle = arena.width / 2;
+    let y_middle = arena.height / 2;
+    let top_left_quadrant = robots
+        .iter()
+        .filter(|r| r.0 < x_middle && r.1 < y_middle)
+        .count();
+    let top_right_quadrant = robots
+        .iter()
+        .filter(|r| r.0 > x_middle && r.1 < y_middle)
+        .count();
+    let bottom_left_quadrant = robots
+        .iter()
+        .filter(|r| r.0 < x_middle && r.1 > y_middle)
+        .count();
+    let bottom_right_quadrant = robots
+        .iter()
+        .filter(|r| r.0 > x_middle && r.1 > y_middle)
+        .count();
+
+    top_left_quadrant * top_right_quadrant * bottom_left_quadrant * bottom_right_quadrant
+}
+
+/// Returns the first second at which the robots are most tightly clustered, which is when they
+/// draw the picture. The arena dimensions must be coprime.
+pub fn find_picture(input: &str, arena: Arena) -> usize {
+    let robots = parse(input).collect::<Vec<_>>();
+
+    // The x coordinates repeat every `width` steps and the y coordinates every `height` steps.
+    // When the robots form a picture they are clustered together, so find the step where each
+    // axis is least spread out and combine them.
+    let best_x = (0..arena.width)
+        .min_by_key(|&t| spread(robots.iter().map(|r| r.position_at(t, arena).0)))
+        .unwrap();
+    let best_y = (0..arena.height)
+        .min_by_key(|&t| spread(robots.iter().map(|r| r.position_at(t, arena).1)))
+        .unwrap();
+
+    math::chinese_remainder(
+        (best_x as i128, arena.width as i128),
+        (best_y as i128, arena.height as i128),
+    )
+    .expect("arena dimensions should be coprime") as usize
+}
 
-        panic!("nothing found");
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Robot {
+    pub position: (isize, isize),
+    pub velocity: (isize, isize),
+}
+
+impl Robot {
+    /// Returns where this robot is after `t` seconds, teleporting around the edges of the arena.
+    pub fn position_at(&self, t: isize, arena: Arena) -> (isize, isize) {
+        (
+            (self.position.0 + self.velocity.0 * t).rem_euclid(arena.width),
+            (self.position.1 + self.velocity.1 * t).rem_euclid(arena.height),
+        )
     }
 }
 
+pub fn parse(input: &str) -> impl Iterator<Item = Robot> + '_ {
+    input.lines().map(|line| {
+        let (p, v) = line.split_once(' ').unwrap();
+        let p = p.split_once('=').unwrap().1.split_once(',').unwrap();
+        let v = v.split_once('=').unwrap().1.split_once(',').unwrap();
+
+        Robot {
+            position: (p.0.parse::<isize>().unwrap(), p.1.parse::<isize>().unwrap()),
+            velocity: (v.0.parse::<isize>().unwrap(), v.1.parse::<isize>().unwrap()),
+        }
+    })
+}
+
+/// Returns the variance of the values multiplied by the square of their count, which keeps it an
+/// integer while preserving the ordering between different sets of the same size.
+fn spread(values: impl Iterator<Item = isize>) -> isize {
+    let (count, sum, sum_of_squares) = values.fold((0, 0, 0), |(count, sum, squares), v| {
+        (count + 1, sum + v, squares + v * v)
+    });
+    count * sum_of_squares - sum * sum
+}
+
 #[test]
 fn test_silver_sample() {
-    let output = Day::calculate_silver(Day::INPUT_SAMPLE);
-    assert_eq!(21, output);
+    let output = Day::calculate_silver_sample(Day::INPUT_SAMPLE);
+    assert_eq!(12, output);
 }
 
 #[test]
@@ -137,8 +157,10 @@ fn test_silver_real() {
 
 #[test]
 fn test_gold_sample() {
-    let output = Day::calculate_gold(Day::INPUT_SAMPLE_GOLD);
-    assert_eq!(0, output);
+    // the sample doesn't draw a picture, but the robots are most clustered after 24 seconds, where
+    // the x spread is lowest at 2 (mod 11) and the y spread at 3 (mod 7)
+    let output = Day::calculate_gold_sample(Day::INPUT_SAMPLE_GOLD);
+    assert_eq!(24, output);
 }
 
 #[test]
@@ -146,3 +168,19 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(7286, output);
 }
+
+#[test]
+fn test_position_at() {
+    // the example from the puzzle description
+    let robot = Robot {
+        position: (2, 4),
+        velocity: (2, -3),
+    };
+    let positions = (0..=5)
+        .map(|t| robot.position_at(t, Arena::SAMPLE))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![(2, 4), (4, 1), (6, 5), (8, 2), (10, 6), (1, 3)],
+        positions
+    );
+}
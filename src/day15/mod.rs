@@ -8,223 +8,371 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let (grid, instructions) = input.split_once("\n\n").unwrap();
-        let instructions = instructions.lines().collect::<String>();
-
-        let mut grid = grid.as_bytes().to_vec();
-        let width = grid.iter().position(|&c| c == b'\n').unwrap();
-        let stride = width + 1;
-        // let height = (grid.len() + 1) / stride;
-
-        // apply instructions
-        for instruction in instructions.chars() {
-            let current_pos = grid.iter().position(|&c| c == b'@').unwrap();
-            let current_pos_x = (current_pos % stride) as isize;
-            let current_pos_y = (current_pos / stride) as isize;
-            let direction = match instruction {
-                '^' => (0isize, -1isize),
-                'v' => (0, 1),
-                '<' => (-1, 0),
-                '>' => (1, 0),
-                i => panic!("unknown instruction: '{i}'"),
-            };
-            let direction_i = direction.1 * stride as isize + direction.0;
-
-            // see if movable
-            let mut push_count = 0;
-            let mut can_move = true;
-            for i in 1.. {
-                let current_pos_x_i = current_pos_x + direction.0 * i;
-                let current_pos_y_i = current_pos_y + direction.1 * i;
-                let current_pos_i = current_pos_y_i * stride as isize + current_pos_x_i;
-
-                if grid[current_pos_i as usize] == b'#' {
-                    can_move = false;
-                    break;
-                }
-                if grid[current_pos_i as usize] == b'.' {
-                    can_move = true;
-                    break;
-                }
-                if grid[current_pos_i as usize] == b'O' {
-                    push_count += 1;
-                }
-            }
+        let (map, instructions) = parse(input);
+        let mut warehouse = Warehouse::new(map);
+        for direction in instructions {
+            warehouse.step(direction);
+        }
+        warehouse.gps_sum()
+    }
+}
 
-            // println!("[{can_move}] Moving {:?} with push count {:?}",direction, push_count);
+impl SolutionGold<usize, usize> for Day {
+    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
-            // move if possible
-            if can_move {
-                // move the blocks over
-                for i in (0..push_count).rev() {
-                    grid[(current_pos as isize + direction_i * (i + 2)) as usize] =
-                        grid[(current_pos as isize + direction_i * (i + 1)) as usize];
-                }
+    fn calculate_gold(input: &str) -> usize {
+        let (map, instructions) = parse(input);
+        let mut warehouse = Warehouse::new_wide(map);
+        for direction in instructions {
+            warehouse.step(direction);
+        }
+        warehouse.gps_sum()
+    }
+}
 
-                // move player
-                grid[(current_pos as isize + direction_i) as usize] = b'@';
-                grid[current_pos] = b'.';
-            }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn from_instruction(instruction: u8) -> Self {
+        match instruction {
+            b'^' => Direction::Up,
+            b'v' => Direction::Down,
+            b'<' => Direction::Left,
+            b'>' => Direction::Right,
+            i => panic!("unknown instruction: '{}'", i as char),
         }
+    }
 
-        grid.into_iter()
-            .enumerate()
-            .filter(|(_, chr)| *chr == b'O')
-            .map(|(i, _)| {
-                let x = i % stride;
-                let y = i / stride;
-                y * 100 + x
-            })
-            .sum()
+    pub fn to_instruction(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
     }
 }
 
-impl SolutionGold<usize, usize> for Day {
-    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveOutcome {
+    /// The robot moved into an empty cell.
+    Moved,
+    /// The robot moved and pushed this many boxes along with it.
+    Pushed { boxes: usize },
+    /// A wall stopped the robot, or one of the boxes it tried to push.
+    Blocked,
+}
 
-    fn calculate_gold(input: &str) -> usize {
-        let (grid, instructions) = input.split_once("\n\n").unwrap();
-        let instructions = instructions.lines().collect::<String>();
-
-        let grid = grid
-            .replace("#", "##")
-            .replace("O", "[]")
-            .replace(".", "..")
-            .replace("@", "@.");
-        let mut grid = grid.as_bytes().to_vec();
-        let width = grid.iter().position(|&c| c == b'\n').unwrap();
-        let stride = width + 1;
-        // let height = (grid.len() + 1) / stride;
-
-        // apply instructions
-        for instruction in instructions.chars() {
-            let current_pos = grid.iter().position(|&c| c == b'@').unwrap();
-            let current_pos_x = (current_pos % stride) as isize;
-            let current_pos_y = (current_pos / stride) as isize;
-            let direction = match instruction {
-                '^' => (0isize, -1isize),
-                'v' => (0, 1),
-                '<' => (-1, 0),
-                '>' => (1, 0),
-                i => panic!("unknown instruction: '{i}'"),
-            };
-            let direction_i = direction.1 * stride as isize + direction.0;
-
-            // see if movable
-            let mut can_move = false;
-            let mut pushes_box = false;
-            {
-                let current_pos_x_i = current_pos_x + direction.0;
-                let current_pos_y_i = current_pos_y + direction.1;
-                let current_pos_i = current_pos_y_i * stride as isize + current_pos_x_i;
-
-                if grid[current_pos_i as usize] == b'#' {
-                    can_move = false;
-                }
-                if grid[current_pos_i as usize] == b'.' {
-                    can_move = true;
-                }
-                if matches!(grid[current_pos_i as usize], b'[' | b']') {
-                    pushes_box = true;
-                }
-            }
+/// The warehouse map, with either single-width (`O`) or wide (`[]`) boxes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warehouse {
+    /// The map including its newlines, so it can be rendered directly.
+    grid: Vec<u8>,
+    stride: usize,
+    robot: usize,
+}
+
+impl Warehouse {
+    pub fn new(map: &str) -> Self {
+        let mut grid = map.trim_end().as_bytes().to_vec();
+        grid.push(b'\n');
+        let stride = grid.iter().position(|&c| c == b'\n').unwrap() + 1;
+        let robot = grid.iter().position(|&c| c == b'@').unwrap();
+
+        Warehouse {
+            grid,
+            stride,
+            robot,
+        }
+    }
 
-            // move if possible
-            if can_move
-                || (pushes_box
-                    && push_box_recursive(&mut grid, stride, current_pos, direction, false)
-                    && push_box_recursive(&mut grid, stride, current_pos, direction, true))
-            {
-                // move player
-                grid[(current_pos as isize + direction_i) as usize] = b'@';
-                grid[current_pos] = b'.';
+    /// Creates a warehouse where everything except the robot is twice as wide.
+    pub fn new_wide(map: &str) -> Self {
+        let map = map
+            .replace('#', "##")
+            .replace('O', "[]")
+            .replace('.', "..")
+            .replace('@', "@.");
+        Self::new(&map)
+    }
+
+    /// Returns the robot's position as `(x, y)`.
+    pub fn robot(&self) -> (usize, usize) {
+        (self.robot % self.stride, self.robot / self.stride)
+    }
+
+    pub fn step(&mut self, direction: Direction) -> MoveOutcome {
+        self.step_recorded(direction).0
+    }
+
+    /// Like [`Warehouse::step`], but also returns every cell the move changed.
+    pub fn step_recorded(&mut self, direction: Direction) -> (MoveOutcome, Vec<CellChange>) {
+        let mut moving = vec![];
+        if !self.collect_pushed(self.robot, direction, &mut moving) {
+            return (MoveOutcome::Blocked, vec![]);
+        }
+
+        let offset = self.offset(direction);
+        let mut touched = moving
+            .iter()
+            .flat_map(|&pos| [pos, pos.wrapping_add_signed(offset)])
+            .collect::<Vec<_>>();
+        touched.sort_unstable();
+        touched.dedup();
+        let before = touched
+            .iter()
+            .map(|&pos| self.grid[pos])
+            .collect::<Vec<_>>();
+
+        // cells are collected furthest first, so every cell moves into an already vacated spot
+        for &pos in &moving {
+            self.grid[pos.wrapping_add_signed(offset)] = self.grid[pos];
+            self.grid[pos] = b'.';
+        }
+        self.robot = self.robot.wrapping_add_signed(offset);
+
+        let boxes = moving
+            .iter()
+            .filter(|&&pos| matches!(self.grid[pos.wrapping_add_signed(offset)], b'O' | b'['))
+            .count();
+        let outcome = if boxes == 0 {
+            MoveOutcome::Moved
+        } else {
+            MoveOutcome::Pushed { boxes }
+        };
+
+        let changes = touched
+            .into_iter()
+            .zip(before)
+            .filter(|&(pos, before)| self.grid[pos] != before)
+            .map(|(pos, before)| CellChange {
+                x: pos % self.stride,
+                y: pos / self.stride,
+                before,
+                after: self.grid[pos],
+            })
+            .collect();
+        (outcome, changes)
+    }
+
+    /// Writes the given changes back to the map, or reverts them if `undo` is set.
+    fn apply_changes(&mut self, changes: &[CellChange], undo: bool) {
+        for change in changes {
+            let pos = change.y * self.stride + change.x;
+            self.grid[pos] = if undo { change.before } else { change.after };
+            if self.grid[pos] == b'@' {
+                self.robot = pos;
             }
         }
+    }
 
-        grid.into_iter()
+    /// Sums the GPS coordinates of all boxes, measured from their left edge.
+    pub fn gps_sum(&self) -> usize {
+        self.grid
+            .iter()
             .enumerate()
-            .filter(|(_, chr)| *chr == b'[')
-            .map(|(i, _)| {
-                let x = i % stride;
-                let y = i / stride;
-                y * 100 + x
-            })
+            .filter(|(_, &c)| matches!(c, b'O' | b'['))
+            .map(|(i, _)| (i / self.stride) * 100 + i % self.stride)
             .sum()
     }
+
+    pub fn render(&self) -> String {
+        String::from_utf8(self.grid.clone()).unwrap()
+    }
+
+    fn offset(&self, direction: Direction) -> isize {
+        match direction {
+            Direction::Up => -(self.stride as isize),
+            Direction::Down => self.stride as isize,
+            Direction::Left => -1,
+            Direction::Right => 1,
+        }
+    }
+
+    /// Recursively finds every cell that has to move when `pos` moves in `direction`, adding them
+    /// to `moving` after the cells in front of them. Returns false if anything hits a wall.
+    fn collect_pushed(&self, pos: usize, direction: Direction, moving: &mut Vec<usize>) -> bool {
+        if moving.contains(&pos) {
+            return true;
+        }
+
+        let next = pos.wrapping_add_signed(self.offset(direction));
+        let can_move = match self.grid[next] {
+            b'#' => false,
+            b'.' => true,
+            b'O' => self.collect_pushed(next, direction, moving),
+            // pushing a wide box up or down also pushes its other half
+            b'[' if direction.is_vertical() => {
+                self.collect_pushed(next, direction, moving)
+                    && self.collect_pushed(next + 1, direction, moving)
+            }
+            b']' if direction.is_vertical() => {
+                self.collect_pushed(next, direction, moving)
+                    && self.collect_pushed(next - 1, direction, moving)
+            }
+            b'[' | b']' => self.collect_pushed(next, direction, moving),
+            c => panic!("unknown char: '{}'", c as char),
+        };
+
+        if can_move {
+            moving.push(pos);
+        }
+        can_move
+    }
 }
 
-fn push_box_recursive(
-    grid: &mut Vec<u8>,
-    stride: usize,
-    push_pos: usize,
-    push_dir: (isize, isize),
-    do_push: bool,
-) -> bool {
-    let next_pos = push_pos as isize + push_dir.1 * stride as isize + push_dir.0;
-
-    let next_chr = grid[next_pos as usize];
-    match grid[next_pos as usize] {
-        b'#' => return false,
-        b'.' => return true,
-        b'[' => {
-            // left
-        }
-        b']' => {
-            // right
-        }
-        i => panic!("unknown char: '{i}'"),
-    }
-
-    let can_push = if push_dir.1 == 0 {
-        // left/right
-        let next_pos_2 = push_pos as isize + push_dir.1 * 2 * stride as isize + push_dir.0 * 2;
-        push_box_recursive(grid, stride, next_pos_2 as usize, push_dir, do_push)
-    } else {
-        // top/down
-        let other_push_pos = (next_pos
-            + match next_chr {
-                b'[' => 1isize,
-                b']' => -1,
-                _ => panic!("unknown char: '{next_chr}'"),
-            }) as usize;
-
-        let can_push_main = push_box_recursive(grid, stride, next_pos as usize, push_dir, do_push);
-        let can_push_other = push_box_recursive(grid, stride, other_push_pos, push_dir, do_push);
-
-        can_push_main && can_push_other
-    };
+/// A single cell of the map that was changed by a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub x: usize,
+    pub y: usize,
+    pub before: u8,
+    pub after: u8,
+}
 
-    if can_push && do_push {
-        if push_dir.1 == 0 {
-            // left/right
-            let next_pos_3 = push_pos as isize + push_dir.1 * 3 * stride as isize + push_dir.0 * 3;
-            let next_pos_2 = push_pos as isize + push_dir.1 * 2 * stride as isize + push_dir.0 * 2;
-            let next_pos_1 = push_pos as isize + push_dir.1 * 1 * stride as isize + push_dir.0 * 1;
-            grid[next_pos_3 as usize] = grid[next_pos_2 as usize];
-            grid[next_pos_2 as usize] = grid[next_pos_1 as usize];
-            grid[next_pos_1 as usize] = b'.';
-        } else {
-            // top/down
-            let other_box = match next_chr {
-                b'[' => 1isize,
-                b']' => -1,
-                _ => panic!("unknown char: '{next_chr}'"),
-            };
+/// An instruction that was applied to the warehouse, along with what it did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub direction: Direction,
+    pub outcome: MoveOutcome,
+    pub changes: Vec<CellChange>,
+}
+
+/// Records every instruction applied to a warehouse, so the simulation can be stepped backwards and
+/// forwards or exported as a sequence of frames.
+pub struct History {
+    initial: Warehouse,
+    current: Warehouse,
+    records: Vec<Record>,
+    /// How many of the records are currently applied to `current`.
+    position: usize,
+}
+
+impl History {
+    pub fn new(warehouse: Warehouse) -> Self {
+        History {
+            initial: warehouse.clone(),
+            current: warehouse,
+            records: vec![],
+            position: 0,
+        }
+    }
+
+    /// Applies an instruction and records it, discarding anything that was undone before.
+    pub fn apply(&mut self, direction: Direction) -> MoveOutcome {
+        self.records.truncate(self.position);
+        let (outcome, changes) = self.current.step_recorded(direction);
+        self.records.push(Record {
+            direction,
+            outcome,
+            changes,
+        });
+        self.position += 1;
+        outcome
+    }
+
+    /// Reverts the last applied instruction. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.position -= 1;
+        self.current
+            .apply_changes(&self.records[self.position].changes, true);
+        true
+    }
+
+    /// Re-applies the last undone instruction. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if self.position == self.records.len() {
+            return false;
+        }
+        self.current
+            .apply_changes(&self.records[self.position].changes, false);
+        self.position += 1;
+        true
+    }
+
+    /// Moves to the state after the first `step` recorded instructions.
+    pub fn jump_to(&mut self, step: usize) {
+        assert!(step <= self.records.len(), "step {step} was never recorded");
+        while self.position > step {
+            self.undo();
+        }
+        while self.position < step {
+            self.redo();
+        }
+    }
 
-            let next_pos_2 = push_pos as isize + push_dir.1 * 2 * stride as isize + push_dir.0 * 2;
-            let next_pos_1 = push_pos as isize + push_dir.1 * 1 * stride as isize + push_dir.0 * 1;
+    /// Returns how many instructions are currently applied.
+    pub fn position(&self) -> usize {
+        self.position
+    }
 
-            grid[next_pos_2 as usize] = grid[next_pos_1 as usize];
-            grid[(next_pos_2 + other_box) as usize] = grid[(next_pos_1 + other_box) as usize];
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
 
-            grid[next_pos_1 as usize] = b'.';
-            grid[(next_pos_1 + other_box) as usize] = b'.';
+    pub fn warehouse(&self) -> &Warehouse {
+        &self.current
+    }
 
-            // todo!("push top/down");
+    /// Renders the initial map followed by the map after every recorded instruction.
+    pub fn frames(&self) -> Vec<String> {
+        let mut warehouse = self.initial.clone();
+        let mut frames = vec![warehouse.render()];
+        for record in &self.records {
+            warehouse.apply_changes(&record.changes, false);
+            frames.push(warehouse.render());
         }
+        frames
     }
 
-    can_push
+    /// Exports every frame in the same format the puzzle description uses.
+    pub fn to_ascii(&self) -> String {
+        let frames = self.frames();
+        let mut output = format!("Initial state:\n{}", frames[0]);
+        for (record, frame) in self.records.iter().zip(&frames[1..]) {
+            output += &format!("\nMove {}:\n{frame}", record.direction.to_instruction());
+        }
+        output
+    }
+
+    /// Exports every frame as an asciinema v2 recording, showing each one for `frame_duration`
+    /// seconds.
+    pub fn to_asciicast(&self, frame_duration: f64) -> String {
+        let frames = self.frames();
+        let width = self.initial.stride - 1;
+        let height = self.initial.grid.len() / self.initial.stride;
+
+        let mut output = format!("{{\"version\": 2, \"width\": {width}, \"height\": {height}}}\n");
+        for (i, frame) in frames.iter().enumerate() {
+            // move the cursor home and clear the screen before drawing each frame
+            let data = format!("\\u001b[H\\u001b[2J{}", frame.replace('\n', "\\r\\n"));
+            output += &format!("[{:.3}, \"o\", \"{data}\"]\n", i as f64 * frame_duration);
+        }
+        output
+    }
+}
+
+/// Splits the input into the warehouse map and the robot's instructions.
+pub fn parse(input: &str) -> (&str, impl Iterator<Item = Direction> + '_) {
+    let (map, instructions) = input.split_once("\n\n").unwrap();
+    let instructions = instructions
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(Direction::from_instruction);
+    (map, instructions)
 }
 
 #[test]
@@ -250,3 +398,158 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(1386070, output);
 }
+
+#[test]
+fn test_silver_sample_states() {
+    // every intermediate state from the small example in the puzzle description
+    let expected = [
+        "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n",
+        "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n",
+        "########\n#.@O.O.#\n##..O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n",
+        "########\n#.@O.O.#\n##..O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n",
+        "########\n#..@OO.#\n##..O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n",
+        "########\n#...@OO#\n##..O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n",
+        "########\n#...@OO#\n##..O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n",
+        "########\n#....OO#\n##..@..#\n#...O..#\n#.#.O..#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##..@..#\n#...O..#\n#.#.O..#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##.@...#\n#...O..#\n#.#.O..#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##.....#\n#..@O..#\n#.#.O..#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##.....#\n#...@O.#\n#.#.O..#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##.....#\n#....@O#\n#.#.O..#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##.....#\n#.....O#\n#.#.O@.#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##.....#\n#.....O#\n#.#O@..#\n#...O..#\n#...O..#\n########\n",
+        "########\n#....OO#\n##.....#\n#.....O#\n#.#O@..#\n#...O..#\n#...O..#\n########\n",
+    ];
+
+    let (map, instructions) = parse(Day::INPUT_SAMPLE);
+    let mut warehouse = Warehouse::new(map);
+    assert_eq!(expected[0], warehouse.render());
+    for (direction, expected) in instructions.zip(&expected[1..]) {
+        warehouse.step(direction);
+        assert_eq!(*expected, warehouse.render());
+    }
+    assert_eq!((4, 4), warehouse.robot());
+}
+
+#[test]
+fn test_gold_small_sample_states() {
+    // every intermediate state from the small wide example in the puzzle description
+    const INPUT: &str =
+        "#######\n#...#.#\n#.....#\n#..OO@#\n#..O..#\n#.....#\n#######\n\n<vv<<^^<<^^\n";
+    let expected = [
+        "##############\n##......##..##\n##..........##\n##....[][]@.##\n##....[]....##\n##..........##\n##############\n",
+        "##############\n##......##..##\n##..........##\n##...[][]@..##\n##....[]....##\n##..........##\n##############\n",
+        "##############\n##......##..##\n##..........##\n##...[][]...##\n##....[].@..##\n##..........##\n##############\n",
+        "##############\n##......##..##\n##..........##\n##...[][]...##\n##....[]....##\n##.......@..##\n##############\n",
+        "##############\n##......##..##\n##..........##\n##...[][]...##\n##....[]....##\n##......@...##\n##############\n",
+        "##############\n##......##..##\n##..........##\n##...[][]...##\n##....[]....##\n##.....@....##\n##############\n",
+        "##############\n##......##..##\n##...[][]...##\n##....[]....##\n##.....@....##\n##..........##\n##############\n",
+        "##############\n##......##..##\n##...[][]...##\n##....[]....##\n##.....@....##\n##..........##\n##############\n",
+        "##############\n##......##..##\n##...[][]...##\n##....[]....##\n##....@.....##\n##..........##\n##############\n",
+        "##############\n##......##..##\n##...[][]...##\n##....[]....##\n##...@......##\n##..........##\n##############\n",
+        "##############\n##......##..##\n##...[][]...##\n##...@[]....##\n##..........##\n##..........##\n##############\n",
+        "##############\n##...[].##..##\n##...@.[]...##\n##....[]....##\n##..........##\n##..........##\n##############\n",
+    ];
+
+    let (map, instructions) = parse(INPUT);
+    let mut warehouse = Warehouse::new_wide(map);
+    assert_eq!(expected[0], warehouse.render());
+    for (direction, expected) in instructions.zip(&expected[1..]) {
+        warehouse.step(direction);
+        assert_eq!(*expected, warehouse.render());
+    }
+}
+
+#[test]
+fn test_move_outcomes() {
+    let mut warehouse = Warehouse::new_wide("#######\n#.....#\n#.OO@.#\n#.....#\n#######");
+    assert_eq!(
+        MoveOutcome::Pushed { boxes: 2 },
+        warehouse.step(Direction::Left)
+    );
+    assert_eq!(
+        MoveOutcome::Pushed { boxes: 2 },
+        warehouse.step(Direction::Left)
+    );
+    assert_eq!(MoveOutcome::Blocked, warehouse.step(Direction::Left));
+    assert_eq!(MoveOutcome::Moved, warehouse.step(Direction::Up));
+    assert_eq!(MoveOutcome::Blocked, warehouse.step(Direction::Up));
+    assert_eq!((6, 1), warehouse.robot());
+}
+
+#[test]
+fn test_history_undo_and_jump() {
+    let (map, instructions) = parse(Day::INPUT_SAMPLE_GOLD);
+    let instructions = instructions.collect::<Vec<_>>();
+    let mut history = History::new(Warehouse::new_wide(map));
+    for &direction in &instructions {
+        history.apply(direction);
+    }
+    assert_eq!(9021, history.warehouse().gps_sum());
+
+    // jumping to a step should give the same map as replaying up to it
+    for step in [0, 1, 17, 250, 699, instructions.len()] {
+        history.jump_to(step);
+        let mut expected = Warehouse::new_wide(map);
+        for &direction in &instructions[..step] {
+            expected.step(direction);
+        }
+        assert_eq!(&expected, history.warehouse());
+    }
+
+    while history.undo() {}
+    assert_eq!(0, history.position());
+    assert_eq!(Warehouse::new_wide(map), *history.warehouse());
+    assert_eq!(instructions.len() + 1, history.frames().len());
+
+    // applying after an undo discards the undone instructions
+    history.redo();
+    history.apply(Direction::Up);
+    assert_eq!(2, history.records().len());
+    assert!(!history.redo());
+}
+
+#[test]
+fn test_history_changes() {
+    let mut history = History::new(Warehouse::new_wide("#####\n#...#\n#.O.#\n#.@.#\n#####"));
+    assert_eq!(
+        MoveOutcome::Pushed { boxes: 1 },
+        history.apply(Direction::Up)
+    );
+    assert_eq!(MoveOutcome::Blocked, history.apply(Direction::Up));
+
+    let changes = &history.records()[0].changes;
+    let changed = |x, y| {
+        changes
+            .iter()
+            .find(|c| (c.x, c.y) == (x, y))
+            .map(|c| (c.before, c.after))
+    };
+    assert_eq!(5, changes.len());
+    assert_eq!(Some((b'.', b'[')), changed(4, 1));
+    assert_eq!(Some((b'.', b']')), changed(5, 1));
+    assert_eq!(Some((b'[', b'@')), changed(4, 2));
+    assert_eq!(Some((b']', b'.')), changed(5, 2));
+    assert_eq!(Some((b'@', b'.')), changed(4, 3));
+    assert!(history.records()[1].changes.is_empty());
+}
+
+#[test]
+fn test_history_export() {
+    let (map, instructions) = parse(Day::INPUT_SAMPLE);
+    let mut history = History::new(Warehouse::new(map));
+    for direction in instructions.take(2) {
+        history.apply(direction);
+    }
+
+    let expected = "Initial state:\n########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n\n\
+                    Move <:\n########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n\n\
+                    Move ^:\n########\n#.@O.O.#\n##..O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n";
+    assert_eq!(expected, history.to_ascii());
+
+    let cast = history.to_asciicast(0.5);
+    let lines = cast.lines().collect::<Vec<_>>();
+    assert_eq!(4, lines.len());
+    assert_eq!(r#"{"version": 2, "width": 8, "height": 8}"#, lines[0]);
+    assert!(lines[3].starts_with(r#"[1.000, "o", "\u001b[H\u001b[2J########\r\n#.@O.O.#\r\n"#));
+}
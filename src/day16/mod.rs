@@ -1,10 +1,4 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
-    rc::Rc,
-};
-
-use fnv::FnvBuildHasher;
+use std::{cmp::Reverse, collections::BinaryHeap};
 
 use super::*;
 
@@ -16,287 +10,246 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let grid = input.as_bytes();
-        let width = grid.iter().position(|&c| c == b'\n').unwrap();
-        let stride = width + 1;
-
-        let start_pos = grid.iter().position(|&c| c == b'S').unwrap();
-        let end_pos = grid.iter().position(|&c| c == b'E').unwrap();
-
-        let start_dir = (1isize, 0);
-
-        let mut pq = BinaryHeap::<MazeEntrySilver>::new();
-
-        pq.push(MazeEntrySilver {
-            pos: start_pos,
-            dir: start_dir,
-            score: 0,
-        });
-
-        let mut history = HashSet::new();
-        history.insert((start_pos, start_dir));
-
-        while let Some(cur) = pq.pop() {
-            // check if valid
-            if cur.pos == end_pos {
-                return cur.score;
-            }
-
-            let cur_x = (cur.pos % stride) as isize;
-            let cur_y = (cur.pos / stride) as isize;
-
-            // go forward, if possible
-            let next_pos = (cur_y + cur.dir.1) * stride as isize + (cur_x + cur.dir.0);
-            if grid[next_pos as usize] != b'#' && history.insert((next_pos as usize, cur.dir)) {
-                pq.push(MazeEntrySilver {
-                    pos: next_pos as usize,
-                    dir: cur.dir,
-                    score: cur.score + 1,
-                });
-            }
+        Maze::parse(input)
+            .best_score(&MazeRules::default())
+            .unwrap()
+    }
+}
 
-            // try turning left and right
-            let new_dirs = match cur.dir {
-                (0, 1) => [(1, 0), (-1, 0)],
-                (0, -1) => [(-1, 0), (1, 0)],
-                (1, 0) => [(0, -1), (0, 1)],
-                (-1, 0) => [(0, 1), (0, -1)],
-                _ => unreachable!("invalid dir: {:?}", cur.dir),
-            };
-            for new_dir in new_dirs {
-                if history.insert((cur.pos, new_dir)) {
-                    pq.push(MazeEntrySilver {
-                        pos: cur.pos,
-                        dir: new_dir,
-                        score: cur.score + 1000,
-                    });
-                }
-            }
-        }
+impl SolutionGold<usize, usize> for Day {
+    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
-        unreachable!()
+    fn calculate_gold(input: &str) -> usize {
+        Maze::parse(input)
+            .best_paths(&MazeRules::default())
+            .unwrap()
+            .tiles
+            .len()
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct MazeEntrySilver {
-    pos: usize,
-    dir: (isize, isize),
-    score: usize,
+/// Directions in clockwise order, so turning is adding or subtracting one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heading {
+    East,
+    South,
+    West,
+    North,
 }
 
-impl PartialOrd for MazeEntrySilver {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+const DIRECTIONS: usize = 4;
+
+/// How the reindeer is allowed to move through the maze, and what it costs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MazeRules {
+    /// The cost of moving forward one tile. Must be positive.
+    pub step_cost: usize,
+    /// The cost of turning 90 degrees.
+    pub turn_cost: usize,
+    /// Whether the reindeer may turn around, which costs two turns.
+    pub allow_reverse: bool,
+    pub start_heading: Heading,
 }
 
-impl Ord for MazeEntrySilver {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.score.cmp(&other.score).reverse()
+impl Default for MazeRules {
+    fn default() -> Self {
+        MazeRules {
+            step_cost: 1,
+            turn_cost: 1000,
+            allow_reverse: true,
+            start_heading: Heading::East,
+        }
     }
 }
 
-impl SolutionGold<usize, usize> for Day {
-    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
+pub struct Maze {
+    grid: Vec<u8>,
+    stride: usize,
+    start: usize,
+    end: usize,
+}
 
-    fn calculate_gold(input: &str) -> usize {
-        let grid = input.as_bytes();
-        let width = grid.iter().position(|&c| c == b'\n').unwrap();
-        let stride = width + 1;
-
-        let start_pos = grid.iter().position(|&c| c == b'S').unwrap();
-        let end_pos = grid.iter().position(|&c| c == b'E').unwrap();
-
-        let start_x = start_pos % stride;
-        let start_y = start_pos / stride;
-        let end_x = end_pos % stride;
-        let end_y = end_pos / stride;
-
-        let start_dir = (1i8, 0i8);
-
-        let mut pq = BinaryHeap::<MazeEntryGold>::new();
-
-        pq.push(MazeEntryGold {
-            pos: (start_x, start_y),
-            dir: start_dir,
-            cost: 0,
-            distance_to_end: end_x.abs_diff(start_x) + end_y.abs_diff(start_y),
-            visited: LinkedListNode::new((start_pos as u16, start_dir)),
-        });
-
-        let mut global_cache = HashMap::<(u16, (i8, i8)), usize, FnvBuildHasher>::default();
-        global_cache.insert((start_pos as u16, start_dir), 0);
-
-        let mut best_score: Option<usize> = None;
-        let mut positions_in_best_paths = HashSet::new();
-        while let Some(cur) = pq.pop() {
-            // exit if best score is reached
-            if let Some(best_pos) = best_score {
-                if cur.cost > best_pos {
-                    continue;
-                }
-            }
+/// Everything that lies on a cheapest route through the maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestPaths {
+    pub score: usize,
+    /// Every tile that is part of at least one best path, as `(x, y)`.
+    pub tiles: Vec<(usize, usize)>,
+    /// How many different sequences of tiles reach the end with the best score.
+    pub path_count: u128,
+}
 
-            // check if at end
-            if cur.pos == (end_x, end_y) {
-                if let Some(best_score_u) = best_score {
-                    if cur.cost < best_score_u {
-                        best_score = None;
-                        positions_in_best_paths.clear();
-                    }
-                }
+impl Maze {
+    pub fn parse(input: &str) -> Self {
+        let grid = input.as_bytes().to_vec();
+        let stride = grid.iter().position(|&c| c == b'\n').unwrap() + 1;
+        let start = grid.iter().position(|&c| c == b'S').unwrap();
+        let end = grid.iter().position(|&c| c == b'E').unwrap();
+
+        Maze {
+            grid,
+            stride,
+            start,
+            end,
+        }
+    }
 
-                if best_score.is_none() {
-                    best_score = Some(cur.cost);
-                }
+    /// Finds the lowest score to reach the end, or `None` if it can't be reached.
+    pub fn best_score(&self, rules: &MazeRules) -> Option<usize> {
+        let distances = self.dijkstra(rules, &[self.start_state(rules)], false);
+        (0..DIRECTIONS)
+            .map(|dir| distances[self.end * DIRECTIONS + dir])
+            .min()
+            .filter(|&score| score != usize::MAX)
+    }
 
-                positions_in_best_paths.extend(cur.visited.iter().map(|(a, _)| a));
+    /// Finds the best score along with every tile and path that achieves it, or `None` if the end
+    /// can't be reached.
+    pub fn best_paths(&self, rules: &MazeRules) -> Option<BestPaths> {
+        assert!(rules.step_cost > 0, "steps must have a cost");
+
+        // States are a tile and the direction the reindeer was facing when it stepped onto it. A
+        // state is on a best path exactly when the cheapest way to it plus the cheapest way from it
+        // to the end adds up to the best score.
+        let start = self.start_state(rules);
+        let from_start = self.dijkstra(rules, &[start], false);
+        let end_states = (0..DIRECTIONS)
+            .map(|dir| self.end * DIRECTIONS + dir)
+            .collect::<Vec<_>>();
+        let to_end = self.dijkstra(rules, &end_states, true);
+
+        let score = end_states.iter().map(|&state| from_start[state]).min()?;
+        if score == usize::MAX {
+            return None;
+        }
+        let on_best_path = |state: usize| {
+            from_start[state] != usize::MAX
+                && to_end[state] != usize::MAX
+                && from_start[state] + to_end[state] == score
+        };
+
+        let mut best_states = (0..from_start.len())
+            .filter(|&state| on_best_path(state))
+            .collect::<Vec<_>>();
+
+        let mut tiles = best_states
+            .iter()
+            .map(|&state| state / DIRECTIONS)
+            .collect::<Vec<_>>();
+        tiles.dedup();
+        let tiles = tiles
+            .into_iter()
+            .map(|pos| (pos % self.stride, pos / self.stride))
+            .collect();
+
+        // every move costs something, so counting paths in order of distance only ever adds to
+        // states that haven't been visited yet
+        best_states.sort_unstable_by_key(|&state| from_start[state]);
+        let mut counts = vec![0u128; from_start.len()];
+        counts[start] = 1;
+        for state in best_states {
+            if counts[state] == 0 {
                 continue;
             }
-
-            // go forward, if possible
-            let next_pos = ((cur.pos.1 as isize + cur.dir.1 as isize) * stride as isize
-                + (cur.pos.0 as isize + cur.dir.0 as isize)) as usize;
-            if grid[next_pos] != b'#' {
-                let entry = global_cache
-                    .entry((next_pos as u16, cur.dir))
-                    .or_insert(usize::MAX);
-                if *entry < cur.cost + 1 {
-                    continue;
-                }
-
-                *entry = cur.cost + 1;
-
-                let next_pos_x = next_pos % stride;
-                let next_pos_y = next_pos / stride;
-                pq.push(MazeEntryGold {
-                    pos: (next_pos_x, next_pos_y),
-                    dir: cur.dir,
-                    cost: cur.cost + 1,
-                    distance_to_end: next_pos_x.abs_diff(end_x) + next_pos_y.abs_diff(end_y),
-                    visited: cur.visited.clone().with_next((next_pos as u16, cur.dir)),
-                });
-            }
-
-            // try turning left and right
-            let new_dirs = match cur.dir {
-                (0, 1) => [(1, 0), (-1, 0)],
-                (0, -1) => [(-1, 0), (1, 0)],
-                (1, 0) => [(0, -1), (0, 1)],
-                (-1, 0) => [(0, 1), (0, -1)],
-                _ => unreachable!("invalid dir: {:?}", cur.dir),
-            };
-            let cur_pos = cur.pos.1 * stride + cur.pos.0;
-            for new_dir in new_dirs {
-                let probe_pos = (cur_pos as isize
-                    + new_dir.1 as isize * stride as isize
-                    + new_dir.0 as isize) as usize;
-                let probe_pos_x = probe_pos % stride;
-                let probe_pos_y = probe_pos / stride;
-
-                if grid[probe_pos] == b'#' {
-                    continue;
-                }
-
-                let entry = global_cache
-                    .entry((probe_pos as u16, new_dir))
-                    .or_insert(usize::MAX);
-                if *entry < cur.cost + 1001 {
-                    continue;
+            for (next, cost) in self.neighbours(rules, state, false) {
+                if on_best_path(next) && from_start[state] + cost == from_start[next] {
+                    counts[next] = counts[next].saturating_add(counts[state]);
                 }
-
-                *entry = cur.cost + 1001;
-
-                pq.push(MazeEntryGold {
-                    pos: (probe_pos_x, probe_pos_y),
-                    dir: new_dir,
-                    cost: cur.cost + 1001,
-                    distance_to_end: cur.distance_to_end,
-                    visited: cur
-                        .visited
-                        .clone()
-                        .with_next((cur_pos as u16, new_dir))
-                        .with_next((probe_pos as u16, new_dir)),
-                });
             }
         }
-
-        positions_in_best_paths.len()
+        let path_count = end_states
+            .iter()
+            .filter(|&&state| from_start[state] == score)
+            .map(|&state| counts[state])
+            .fold(0u128, |acc, count| acc.saturating_add(count));
+
+        Some(BestPaths {
+            score,
+            tiles,
+            path_count,
+        })
     }
-}
 
-#[derive(Debug, Eq, PartialEq)]
-struct MazeEntryGold {
-    pos: (usize, usize),
-    dir: (i8, i8),
-    cost: usize,
-    distance_to_end: usize,
-    visited: LinkedListNode<(u16, (i8, i8))>,
-}
-
-impl PartialOrd for MazeEntryGold {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Draws the maze with every tile on a best path marked as `O`.
+    pub fn render(&self, paths: &BestPaths) -> String {
+        let mut grid = self.grid.clone();
+        for &(x, y) in &paths.tiles {
+            grid[y * self.stride + x] = b'O';
+        }
+        String::from_utf8(grid).unwrap()
     }
-}
 
-impl Ord for MazeEntryGold {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // self.distance_to_end.cmp(&other.distance_to_end).reverse().then(self.cost.cmp(&other.cost).reverse())
-        self.cost
-            .cmp(&other.cost)
-            .reverse()
-            .then(self.distance_to_end.cmp(&other.distance_to_end).reverse())
-        // (self.cost + self.distance_to_end * 1000).cmp(&(other.cost + other.distance_to_end * 1000)).reverse()
+    fn start_state(&self, rules: &MazeRules) -> usize {
+        self.start * DIRECTIONS + rules.start_heading as usize
     }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct LinkedListNode<T> {
-    current: T,
-    previous: Option<Rc<LinkedListNode<T>>>,
-}
 
-impl<T> LinkedListNode<T> {
-    pub fn new(current: T) -> Self {
-        Self {
-            current,
-            previous: None,
+    fn offset(&self, dir: usize) -> isize {
+        match dir {
+            0 => 1,
+            1 => self.stride as isize,
+            2 => -1,
+            3 => -(self.stride as isize),
+            _ => unreachable!("invalid dir: {dir}"),
         }
     }
 
-    pub fn with_next(self, next: T) -> Self {
-        Self {
-            current: next,
-            previous: Some(Rc::new(self)),
-        }
-    }
+    /// Returns the states reachable with a single step from `state` and what that step costs,
+    /// including any turns beforehand. When `reverse` is set, returns the states that can step
+    /// into `state` instead.
+    fn neighbours<'a>(
+        &'a self,
+        rules: &'a MazeRules,
+        state: usize,
+        reverse: bool,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (pos, dir) = (state / DIRECTIONS, state % DIRECTIONS);
+        (0..DIRECTIONS).filter_map(move |other| {
+            let (from_dir, to_dir) = if reverse { (other, dir) } else { (dir, other) };
+            let next = if reverse {
+                pos.wrapping_add_signed(-self.offset(to_dir))
+            } else {
+                pos.wrapping_add_signed(self.offset(to_dir))
+            };
+            if self.grid[next] == b'#' {
+                return None;
+            }
 
-    pub fn iter(&self) -> LinkedListIterator<T>
-    where
-        T: Clone,
-    {
-        LinkedListIterator {
-            node: Some(Rc::new(self.clone())),
-        }
+            let turns = match (to_dir + DIRECTIONS - from_dir) % DIRECTIONS {
+                0 => 0,
+                1 | 3 => 1,
+                _ if rules.allow_reverse => 2,
+                _ => return None,
+            };
+            Some((
+                next * DIRECTIONS + other,
+                rules.step_cost + turns * rules.turn_cost,
+            ))
+        })
     }
-}
 
-struct LinkedListIterator<T> {
-    node: Option<Rc<LinkedListNode<T>>>,
-}
+    /// Finds the cheapest cost to get from any of the `sources` to every state, or to get from
+    /// every state to any of the `sources` when `reverse` is set.
+    fn dijkstra(&self, rules: &MazeRules, sources: &[usize], reverse: bool) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.grid.len() * DIRECTIONS];
+        let mut pq = BinaryHeap::new();
+        for &source in sources {
+            distances[source] = 0;
+            pq.push(Reverse((0, source)));
+        }
 
-impl<T> Iterator for LinkedListIterator<T>
-where
-    T: Clone,
-{
-    type Item = T;
+        while let Some(Reverse((distance, state))) = pq.pop() {
+            if distance > distances[state] {
+                continue;
+            }
+            for (next, cost) in self.neighbours(rules, state, reverse) {
+                if distance + cost < distances[next] {
+                    distances[next] = distance + cost;
+                    pq.push(Reverse((distance + cost, next)));
+                }
+            }
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let node = self.node.take()?;
-        self.node = node.previous.clone();
-        Some(node.current.clone())
+        distances
     }
 }
 
@@ -323,3 +276,207 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(531, output);
 }
+
+#[test]
+fn test_render_best_paths() {
+    // the first example from the puzzle description
+    let expected = "\
+###############
+#.......#....O#
+#.#.###.#.###O#
+#.....#.#...#O#
+#.###.#####.#O#
+#.#.#.......#O#
+#.#.#####.###O#
+#..OOOOOOOOO#O#
+###O#O#####O#O#
+#OOO#O....#O#O#
+#O#O#O###.#O#O#
+#OOOOO#...#O#O#
+#O###.#.#.#O#O#
+#O..#.....#OOO#
+###############";
+    let maze = Maze::parse(Day::INPUT_SAMPLE_GOLD);
+    let paths = maze.best_paths(&MazeRules::default()).unwrap();
+    assert_eq!(7036, paths.score);
+    assert_eq!(3, paths.path_count);
+    assert_eq!(expected, maze.render(&paths));
+}
+
+#[test]
+fn test_path_count() {
+    // going around either side of the wall takes the same three turns and eight steps
+    let maze = Maze::parse("#######\n#.....#\n#.###.#\n#S###E#\n#.###.#\n#.....#\n#######\n");
+    let paths = maze.best_paths(&MazeRules::default()).unwrap();
+    assert_eq!(3008, paths.score);
+    assert_eq!(2, paths.path_count);
+    assert_eq!(16, paths.tiles.len());
+
+    let maze = Maze::parse("#####\n#S#E#\n#####\n");
+    assert_eq!(None, maze.best_paths(&MazeRules::default()));
+    assert_eq!(None, maze.best_score(&MazeRules::default()));
+}
+
+#[test]
+fn test_maze_rules() {
+    let maze = Maze::parse(Day::INPUT_SAMPLE);
+    let rules = MazeRules {
+        step_cost: 1,
+        turn_cost: 0,
+        ..Default::default()
+    };
+    // without turn costs this is the shortest path through the maze
+    assert_eq!(Some(28), maze.best_score(&rules));
+
+    // a dead end can only be left by turning around
+    let maze = Maze::parse("#####\n#.SE#\n#####\n");
+    let rules = MazeRules {
+        start_heading: Heading::West,
+        ..Default::default()
+    };
+    assert_eq!(Some(2001), maze.best_score(&rules));
+    let rules = MazeRules {
+        allow_reverse: false,
+        ..rules
+    };
+    assert_eq!(None, maze.best_score(&rules));
+    assert_eq!(None, maze.best_paths(&rules));
+
+    // without reversing, the only way to the end is around the loop and back through the start
+    let input = "#######\n#...###\n#.#.###\n#...SE#\n#######\n";
+    let maze = Maze::parse(input);
+    let paths = maze.best_paths(&rules).unwrap();
+    assert_eq!(4011, paths.score);
+    assert_eq!(2, paths.path_count);
+    let (score, brute_force_paths) = brute_force_best_paths(input, &rules).unwrap();
+    assert_eq!(4011, score);
+    assert_eq!(2, brute_force_paths.len());
+}
+
+#[cfg(test)]
+fn generate_random_maze(rng: &mut utils::XorShift, size: usize) -> String {
+    let mut grid = vec![vec![b'#'; size + 2]; size + 2];
+    for row in &mut grid[1..=size] {
+        for cell in &mut row[1..=size] {
+            if rng.next_below(4) != 0 {
+                *cell = b'.';
+            }
+        }
+    }
+    let mut place = |c| loop {
+        let (x, y) = (
+            rng.next_below(size as u64) as usize + 1,
+            rng.next_below(size as u64) as usize + 1,
+        );
+        if grid[y][x] == b'.' {
+            grid[y][x] = c;
+            break;
+        }
+    };
+    place(b'S');
+    place(b'E');
+
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+/// Tries every path that never visits the same tile with the same heading twice, returning the
+/// best score and the tiles of the paths that achieve it. Without reversing, a best path can pass
+/// the same tile twice to change its heading.
+#[cfg(test)]
+fn brute_force_best_paths(input: &str, rules: &MazeRules) -> Option<(usize, Vec<Vec<usize>>)> {
+    fn visit(
+        maze: &Maze,
+        rules: &MazeRules,
+        cost: usize,
+        path: &mut Vec<usize>,
+        best: &mut Option<(usize, Vec<Vec<usize>>)>,
+    ) {
+        // every remaining step costs at least `step_cost`, so prune on the manhattan distance
+        let state = *path.last().unwrap();
+        let (pos, end) = (state / DIRECTIONS, maze.end);
+        let remaining = (pos % maze.stride).abs_diff(end % maze.stride)
+            + (pos / maze.stride).abs_diff(end / maze.stride);
+        if best
+            .as_ref()
+            .is_some_and(|(score, _)| cost + remaining * rules.step_cost > *score)
+        {
+            return;
+        }
+
+        if state / DIRECTIONS == maze.end {
+            let tiles = path.iter().map(|&state| state / DIRECTIONS).collect();
+            match best {
+                Some((score, paths)) if *score == cost => paths.push(tiles),
+                _ => *best = Some((cost, vec![tiles])),
+            }
+            return;
+        }
+        // trying the cheapest moves first finds a tight bound early
+        let mut neighbours: Vec<_> = maze.neighbours(rules, state, false).collect();
+        neighbours.sort_unstable_by_key(|&(_, step)| step);
+        for (next, step) in neighbours {
+            if !path.contains(&next) {
+                path.push(next);
+                visit(maze, rules, cost + step, path, best);
+                path.pop();
+            }
+        }
+    }
+
+    let maze = Maze::parse(input);
+    let mut best = None;
+    visit(
+        &maze,
+        rules,
+        0,
+        &mut vec![maze.start_state(rules)],
+        &mut best,
+    );
+    best
+}
+
+#[test]
+fn test_best_paths_against_brute_force() {
+    let mut rng = utils::XorShift::new(16);
+    for _ in 0..300 {
+        let input = generate_random_maze(&mut rng, 4);
+        let rules = MazeRules {
+            step_cost: rng.next_below(5) as usize + 1,
+            turn_cost: rng.next_below(20) as usize,
+            allow_reverse: rng.next_below(2) == 0,
+            start_heading: [Heading::East, Heading::South, Heading::West, Heading::North]
+                [rng.next_below(4) as usize],
+        };
+
+        let maze = Maze::parse(&input);
+        let score = maze.best_score(&rules);
+        let paths = maze.best_paths(&rules);
+        let Some((expected_score, expected_paths)) = brute_force_best_paths(&input, &rules) else {
+            assert_eq!(None, score, "{input}");
+            assert_eq!(None, paths, "{input}");
+            continue;
+        };
+
+        // the silver score is the minimum over every path found for gold
+        let paths = paths.unwrap();
+        assert_eq!(Some(expected_score), score, "{input}{rules:?}");
+        assert_eq!(expected_score, paths.score, "{input}{rules:?}");
+        assert_eq!(
+            expected_paths.len() as u128,
+            paths.path_count,
+            "{input}{rules:?}"
+        );
+
+        let mut expected_tiles = expected_paths.into_iter().flatten().collect::<Vec<_>>();
+        expected_tiles.sort_unstable();
+        expected_tiles.dedup();
+        let tiles = paths
+            .tiles
+            .iter()
+            .map(|&(x, y)| y * maze.stride + x)
+            .collect::<Vec<_>>();
+        assert_eq!(expected_tiles, tiles, "{input}{rules:?}");
+    }
+}
@@ -1,9 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::VecDeque;
 
 use utils::fast_parse_int;
 
 use super::*;
 
+pub mod reference;
+
 pub struct Day;
 
 impl SolutionSilver<usize> for Day {
@@ -12,198 +14,286 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let nums = input
-            .lines()
-            .map(|line| line.split_once(',').unwrap())
-            .map(|(a, b)| (fast_parse_int(a), fast_parse_int(b)))
-            .collect::<Vec<_>>();
-
-        let start_pos = (0, 0);
-        let end_pos = if nums.len() > 100 { (70, 70) } else { (6, 6) };
-
-        let mut pq = std::collections::BinaryHeap::<Node>::new();
-        pq.push(Node {
-            pos: start_pos,
-            dist: 0,
-        });
-
-        let blocks = nums
-            .iter()
-            .take(if nums.len() > 100 { 1024 } else { 12 })
-            .map(|(x, y)| (*x as u8, *y as u8))
-            .collect::<HashSet<(u8, u8)>>();
-
-        let mut visited = HashMap::<(u8, u8), usize, _>::new();
-
-        while let Some(next) = pq.pop() {
-            if next.pos == end_pos {
-                return next.dist;
-            }
+        steps_to_exit(input, MemorySpace::default())
+    }
 
-            let (x, y) = next.pos;
-            let dist = next.dist + 1;
-
-            if x < end_pos.0 && !blocks.contains(&(x + 1, y)) {
-                let entry = visited.entry((x + 1, y)).or_insert(usize::MAX);
-                if *entry > dist {
-                    *entry = dist;
-                    pq.push(Node {
-                        pos: (x + 1, y),
-                        dist,
-                    });
-                }
-            }
-            if y < end_pos.1 && !blocks.contains(&(x, y + 1)) {
-                let entry = visited.entry((x, y + 1)).or_insert(usize::MAX);
-                if *entry > dist {
-                    *entry = dist;
-                    pq.push(Node {
-                        pos: (x, y + 1),
-                        dist,
-                    });
-                }
-            }
-            if x > 0 && !blocks.contains(&(x - 1, y)) {
-                let entry = visited.entry((x - 1, y)).or_insert(usize::MAX);
-                if *entry > dist {
-                    *entry = dist;
-                    pq.push(Node {
-                        pos: (x - 1, y),
-                        dist,
-                    });
-                }
+    fn calculate_silver_sample(input: &str) -> usize {
+        steps_to_exit(input, MemorySpace::SAMPLE)
+    }
+}
+
+impl SolutionGold<usize, String> for Day {
+    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
+
+    fn calculate_gold(input: &str) -> String {
+        // the answer is left empty if the exit is never cut off
+        first_blocking_byte(input, MemorySpace::default()).unwrap_or_default()
+    }
+
+    fn calculate_gold_sample(input: &str) -> String {
+        first_blocking_byte(input, MemorySpace::SAMPLE).unwrap_or_default()
+    }
+}
+
+/// The dimensions of the memory space and how many bytes fall before escaping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemorySpace {
+    /// The number of cells along each side of the square grid.
+    pub size: usize,
+    /// How many bytes have fallen when the shortest path is measured.
+    pub fallen: usize,
+}
+
+impl MemorySpace {
+    /// The memory space used by the real puzzle input.
+    pub const REAL: MemorySpace = MemorySpace {
+        size: 71,
+        fallen: 1024,
+    };
+    /// The smaller memory space used by the example in the puzzle description.
+    pub const SAMPLE: MemorySpace = MemorySpace {
+        size: 7,
+        fallen: 12,
+    };
+}
+
+impl Default for MemorySpace {
+    fn default() -> Self {
+        MemorySpace::REAL
+    }
+}
+
+/// Finds a shortest path from the top left to the bottom right corner once `space.fallen` bytes
+/// have fallen, as a list of `(x, y)` coordinates including both corners.
+pub fn shortest_path(bytes: &[(usize, usize)], space: MemorySpace) -> Option<Vec<(usize, usize)>> {
+    let size = space.size;
+    let fallen_at = fall_times(bytes, size);
+    let is_open = |cell: usize| fallen_at[cell] >= space.fallen;
+    let end = size * size - 1;
+    if !is_open(0) {
+        return None;
+    }
+
+    // breadth first search, remembering where every cell was first reached from
+    let mut came_from = vec![usize::MAX; size * size];
+    came_from[0] = 0;
+    let mut queue = VecDeque::from([0]);
+    while let Some(cell) = queue.pop_front() {
+        if cell == end {
+            let mut path = vec![end];
+            while *path.last().unwrap() != 0 {
+                path.push(came_from[*path.last().unwrap()]);
             }
-            if y > 0 && !blocks.contains(&(x, y - 1)) {
-                let entry = visited.entry((x, y - 1)).or_insert(usize::MAX);
-                if *entry > dist {
-                    *entry = dist;
-                    pq.push(Node {
-                        pos: (x, y - 1),
-                        dist,
-                    });
-                }
+            return Some(
+                path.into_iter()
+                    .rev()
+                    .map(|c| (c % size, c / size))
+                    .collect(),
+            );
+        }
+        for next in neighbours(cell, size) {
+            if is_open(next) && came_from[next] == usize::MAX {
+                came_from[next] = cell;
+                queue.push_back(next);
             }
         }
+    }
+
+    None
+}
 
-        unreachable!();
+/// Draws the memory space after `space.fallen` bytes, with corrupted cells as `#` and the path
+/// as `O`.
+pub fn render(bytes: &[(usize, usize)], space: MemorySpace, path: &[(usize, usize)]) -> String {
+    let mut grid = vec![vec![b'.'; space.size]; space.size];
+    for &(x, y) in bytes.iter().take(space.fallen) {
+        grid[y][x] = b'#';
     }
+    for &(x, y) in path {
+        grid[y][x] = b'O';
+    }
+
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+/// Returns the number of steps to the exit once `space.fallen` bytes have fallen.
+fn steps_to_exit(input: &str, space: MemorySpace) -> usize {
+    let path = shortest_path(&parse(input), space).expect("the exit should be reachable");
+    path.len() - 1
+}
+
+/// Returns the coordinates of the first byte that cuts off the exit, formatted as `x,y`, or
+/// `None` if the exit stays reachable.
+pub fn first_blocking_byte(input: &str, space: MemorySpace) -> Option<String> {
+    let bytes = parse(input);
+    let (x, y) = bytes[first_blocking_union_find(&bytes, space.size)?];
+    Some(format!("{x},{y}"))
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Node {
-    pos: (u8, u8),
-    dist: usize,
+pub fn parse(input: &str) -> Vec<(usize, usize)> {
+    input
+        .lines()
+        .map(|line| line.split_once(',').unwrap())
+        .map(|(a, b)| (fast_parse_int(a), fast_parse_int(b)))
+        .collect()
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+/// Returns for every cell of a `size` by `size` grid the index of the first byte that falls on it,
+/// or `usize::MAX` if none does.
+fn fall_times(bytes: &[(usize, usize)], size: usize) -> Vec<usize> {
+    let mut fallen_at = vec![usize::MAX; size * size];
+    for (i, &(x, y)) in bytes.iter().enumerate() {
+        let cell = &mut fallen_at[y * size + x];
+        *cell = (*cell).min(i);
     }
+    fallen_at
 }
 
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let dist_from_start = (self.pos.0 + self.pos.1)
-            .cmp(&(other.pos.0 + other.pos.1))
-            // .reverse()
-            ;
-        self.dist.cmp(&other.dist).reverse().then(dist_from_start)
+fn neighbours(cell: usize, size: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (cell % size, cell / size);
+    [
+        (x > 0).then(|| cell - 1),
+        (x + 1 < size).then(|| cell + 1),
+        (y > 0).then(|| cell - size),
+        (y + 1 < size).then(|| cell + size),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Checks whether the bottom right corner can be reached from the top left one after the first
+/// `count` bytes have fallen.
+fn is_reachable(fallen_at: &[usize], size: usize, count: usize) -> bool {
+    let is_open = |cell: usize| fallen_at[cell] >= count;
+    if !is_open(0) {
+        return false;
+    }
+
+    let mut visited = vec![false; fallen_at.len()];
+    visited[0] = true;
+    let mut stack = vec![0];
+    while let Some(cell) = stack.pop() {
+        if cell == fallen_at.len() - 1 {
+            return true;
+        }
+        for next in neighbours(cell, size) {
+            if is_open(next) && !visited[next] {
+                visited[next] = true;
+                stack.push(next);
+            }
+        }
     }
+
+    false
 }
 
-impl SolutionGold<usize, String> for Day {
-    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
+/// Returns the index of the first byte that cuts off the exit, by binary searching over how many
+/// bytes have fallen. Every check is a single flood fill over a flat grid.
+pub fn first_blocking_binary_search(bytes: &[(usize, usize)], size: usize) -> Option<usize> {
+    let fallen_at = fall_times(bytes, size);
+    if is_reachable(&fallen_at, size, bytes.len()) {
+        return None;
+    }
 
-    fn calculate_gold(input: &str) -> String {
-        let nums = input
-            .lines()
-            .map(|line| line.split_once(',').unwrap())
-            .map(|(a, b)| (fast_parse_int(a), fast_parse_int(b)))
-            .collect::<Vec<_>>();
-
-        let start_pos = (0, 0);
-        let end_pos = if nums.len() > 100 { (70, 70) } else { (6, 6) };
-        let start_i = if nums.len() > 100 { 1024 } else { 12 };
-
-        for i in start_i..nums.len() {
-            let mut pq = std::collections::BinaryHeap::<Node>::new();
-            pq.push(Node {
-                pos: start_pos,
-                dist: 0,
-            });
-
-            let blocks = nums
-                .iter()
-                .take(i)
-                .map(|(x, y)| (*x as u8, *y as u8))
-                .collect::<HashSet<(u8, u8)>>();
-
-            let mut visited = HashMap::<(u8, u8), usize, _>::new();
-
-            let mut found_exit = false;
-            while let Some(next) = pq.pop() {
-                if next.pos == end_pos {
-                    found_exit = true;
-                    break;
-                }
+    // the exit is reachable after `low` bytes have fallen, but not after `high`
+    let (mut low, mut high) = (0, bytes.len());
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if is_reachable(&fallen_at, size, middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
 
-                let (x, y) = next.pos;
-                let dist = next.dist + 1;
-
-                if x < end_pos.0 && !blocks.contains(&(x + 1, y)) {
-                    let entry = visited.entry((x + 1, y)).or_insert(usize::MAX);
-                    if *entry > dist {
-                        *entry = dist;
-                        pq.push(Node {
-                            pos: (x + 1, y),
-                            dist,
-                        });
-                    }
-                }
-                if y < end_pos.1 && !blocks.contains(&(x, y + 1)) {
-                    let entry = visited.entry((x, y + 1)).or_insert(usize::MAX);
-                    if *entry > dist {
-                        *entry = dist;
-                        pq.push(Node {
-                            pos: (x, y + 1),
-                            dist,
-                        });
-                    }
-                }
-                if x > 0 && !blocks.contains(&(x - 1, y)) {
-                    let entry = visited.entry((x - 1, y)).or_insert(usize::MAX);
-                    if *entry > dist {
-                        *entry = dist;
-                        pq.push(Node {
-                            pos: (x - 1, y),
-                            dist,
-                        });
-                    }
-                }
-                if y > 0 && !blocks.contains(&(x, y - 1)) {
-                    let entry = visited.entry((x, y - 1)).or_insert(usize::MAX);
-                    if *entry > dist {
-                        *entry = dist;
-                        pq.push(Node {
-                            pos: (x, y - 1),
-                            dist,
-                        });
-                    }
+    Some(high - 1)
+}
+
+/// Returns the index of the first byte that cuts off the exit, by letting every byte fall and then
+/// removing them again from last to first until the start and exit are connected.
+pub fn first_blocking_union_find(bytes: &[(usize, usize)], size: usize) -> Option<usize> {
+    let mut fallen_at = fall_times(bytes, size);
+    let (start, end) = (0, size * size - 1);
+
+    // connect all cells that are still open once every byte has fallen
+    let mut sets = DisjointSet::new(size * size);
+    for cell in 0..size * size {
+        if fallen_at[cell] == usize::MAX {
+            for next in neighbours(cell, size) {
+                if fallen_at[next] == usize::MAX {
+                    sets.union(cell, next);
                 }
             }
+        }
+    }
+    let is_connected = |sets: &mut DisjointSet, fallen_at: &[usize]| {
+        fallen_at[start] == usize::MAX && sets.find(start) == sets.find(end)
+    };
+    if is_connected(&mut sets, &fallen_at) {
+        return None;
+    }
+
+    for (i, &(x, y)) in bytes.iter().enumerate().rev() {
+        let cell = y * size + x;
+        // a cell only opens up again once its first byte is removed
+        if fallen_at[cell] != i {
+            continue;
+        }
 
-            if !found_exit {
-                return format!("{},{}", nums[i - 1].0, nums[i - 1].1);
+        fallen_at[cell] = usize::MAX;
+        for next in neighbours(cell, size) {
+            if fallen_at[next] == usize::MAX {
+                sets.union(cell, next);
             }
         }
+        if is_connected(&mut sets, &fallen_at) {
+            return Some(i);
+        }
+    }
 
-        unreachable!("no solution found")
+    unreachable!("the grid is fully open without any bytes")
+}
+
+struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> Self {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+        }
+    }
+
+    fn find(&mut self, mut item: usize) -> usize {
+        while self.parents[item] != item {
+            // path halving
+            self.parents[item] = self.parents[self.parents[item]];
+            item = self.parents[item];
+        }
+        item
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
     }
 }
 
 #[test]
 fn test_silver_sample() {
-    let output = Day::calculate_silver(Day::INPUT_SAMPLE);
+    let output = Day::calculate_silver_sample(Day::INPUT_SAMPLE);
     assert_eq!(22, output);
 }
 
@@ -215,8 +305,14 @@ fn test_silver_real() {
 
 #[test]
 fn test_gold_sample() {
-    let output = Day::calculate_gold(Day::INPUT_SAMPLE_GOLD);
+    let output = Day::calculate_gold_sample(Day::INPUT_SAMPLE_GOLD);
     assert_eq!("6,1", output);
+
+    // in the larger memory space of the real puzzle, the sample bytes never cut off the exit
+    assert_eq!(
+        None,
+        first_blocking_byte(Day::INPUT_SAMPLE_GOLD, MemorySpace::REAL)
+    );
 }
 
 #[test]
@@ -224,3 +320,89 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!("46,18", output);
 }
+
+#[cfg(test)]
+fn generate_bytes(rng: &mut utils::XorShift, size: usize) -> Vec<(usize, usize)> {
+    (0..size * size)
+        .map(|_| {
+            (
+                rng.next_below(size as u64) as usize,
+                rng.next_below(size as u64) as usize,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_gold_against_reference() {
+    assert_eq!(
+        Some(reference::calculate_gold(Day::INPUT_SAMPLE_GOLD)),
+        first_blocking_byte(Day::INPUT_SAMPLE_GOLD, MemorySpace::SAMPLE)
+    );
+    assert_eq!(
+        Some(reference::calculate_gold(Day::INPUT_REAL)),
+        first_blocking_byte(Day::INPUT_REAL, MemorySpace::REAL)
+    );
+}
+
+#[test]
+fn test_first_blocking_byte() {
+    let mut rng = utils::XorShift::new(18);
+    for size in (1..20).cycle().take(200) {
+        let bytes = generate_bytes(&mut rng, size);
+        let fallen_at = fall_times(&bytes, size);
+        let expected = (0..=bytes.len())
+            .find(|&count| !is_reachable(&fallen_at, size, count))
+            .map(|count| count - 1);
+
+        assert_eq!(
+            expected,
+            first_blocking_binary_search(&bytes, size),
+            "{bytes:?}"
+        );
+        assert_eq!(
+            expected,
+            first_blocking_union_find(&bytes, size),
+            "{bytes:?}"
+        );
+    }
+
+    // the exit stays open if the bytes never cut it off
+    assert_eq!(None, first_blocking_binary_search(&[(1, 0)], 2));
+    assert_eq!(None, first_blocking_union_find(&[(1, 0)], 2));
+}
+
+#[test]
+fn test_shortest_path() {
+    let bytes = parse(Day::INPUT_SAMPLE);
+    let path = shortest_path(&bytes, MemorySpace::SAMPLE).unwrap();
+    assert_eq!((0, 0), path[0]);
+    assert_eq!((6, 6), path[path.len() - 1]);
+    for step in path.windows(2) {
+        assert_eq!(
+            1,
+            step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1)
+        );
+    }
+
+    // the corrupted memory from the puzzle description, with the path drawn over it
+    let rendered = render(&bytes, MemorySpace::SAMPLE, &path);
+    let corrupted = rendered.replace('O', ".");
+    assert_eq!(
+        "...#...\n..#..#.\n....#..\n...#..#\n..#..#.\n.#..#..\n#.#....\n",
+        corrupted
+    );
+    assert_eq!(path.len(), rendered.matches('O').count());
+
+    // after the blocking byte has fallen there is no way through
+    let space = MemorySpace {
+        fallen: 21,
+        ..MemorySpace::SAMPLE
+    };
+    assert_eq!(None, shortest_path(&bytes, space));
+    let space = MemorySpace {
+        fallen: 20,
+        ..MemorySpace::SAMPLE
+    };
+    assert!(shortest_path(&bytes, space).is_some());
+}
@@ -0,0 +1,113 @@
+//! The original gold, which reruns Dijkstra on a freshly built set of blocks for every byte count.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::utils::fast_parse_int;
+
+pub fn calculate_gold(input: &str) -> String {
+    let nums = input
+        .lines()
+        .map(|line| line.split_once(',').unwrap())
+        .map(|(a, b)| (fast_parse_int(a), fast_parse_int(b)))
+        .collect::<Vec<_>>();
+
+    let start_pos = (0, 0);
+    let end_pos = if nums.len() > 100 { (70, 70) } else { (6, 6) };
+    let start_i = if nums.len() > 100 { 1024 } else { 12 };
+
+    for i in start_i..nums.len() {
+        let mut pq = std::collections::BinaryHeap::<Node>::new();
+        pq.push(Node {
+            pos: start_pos,
+            dist: 0,
+        });
+
+        let blocks = nums
+            .iter()
+            .take(i)
+            .map(|(x, y)| (*x as u8, *y as u8))
+            .collect::<HashSet<(u8, u8)>>();
+
+        let mut visited = HashMap::<(u8, u8), usize, _>::new();
+
+        let mut found_exit = false;
+        while let Some(next) = pq.pop() {
+            if next.pos == end_pos {
+                found_exit = true;
+                break;
+            }
+
+            let (x, y) = next.pos;
+            let dist = next.dist + 1;
+
+            if x < end_pos.0 && !blocks.contains(&(x + 1, y)) {
+                let entry = visited.entry((x + 1, y)).or_insert(usize::MAX);
+                if *entry > dist {
+                    *entry = dist;
+                    pq.push(Node {
+                        pos: (x + 1, y),
+                        dist,
+                    });
+                }
+            }
+            if y < end_pos.1 && !blocks.contains(&(x, y + 1)) {
+                let entry = visited.entry((x, y + 1)).or_insert(usize::MAX);
+                if *entry > dist {
+                    *entry = dist;
+                    pq.push(Node {
+                        pos: (x, y + 1),
+                        dist,
+                    });
+                }
+            }
+            if x > 0 && !blocks.contains(&(x - 1, y)) {
+                let entry = visited.entry((x - 1, y)).or_insert(usize::MAX);
+                if *entry > dist {
+                    *entry = dist;
+                    pq.push(Node {
+                        pos: (x - 1, y),
+                        dist,
+                    });
+                }
+            }
+            if y > 0 && !blocks.contains(&(x, y - 1)) {
+                let entry = visited.entry((x, y - 1)).or_insert(usize::MAX);
+                if *entry > dist {
+                    *entry = dist;
+                    pq.push(Node {
+                        pos: (x, y - 1),
+                        dist,
+                    });
+                }
+            }
+        }
+
+        if !found_exit {
+            return format!("{},{}", nums[i - 1].0, nums[i - 1].1);
+        }
+    }
+
+    unreachable!("no solution found")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Node {
+    pos: (u8, u8),
+    dist: usize,
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let dist_from_start = (self.pos.0 + self.pos.1)
+            .cmp(&(other.pos.0 + other.pos.1))
+            // .reverse()
+            ;
+        self.dist.cmp(&other.dist).reverse().then(dist_from_start)
+    }
+}
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
-
 use super::*;
 
+pub mod reference;
+
 pub struct Day;
 
 impl SolutionSilver<usize> for Day {
@@ -10,102 +10,327 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let (patterns, designs) = input.split_once("\n\n").unwrap();
-
-        let mut patterns = patterns.split(", ").collect::<Vec<_>>();
+        let (towels, designs) = parse(input);
+        designs
+            .filter(|design| towels.count_arrangements(design) > 0)
+            .count()
+    }
 
-        patterns.sort_by_key(|p| p.len());
+    fn explain_silver(input: &str) -> Option<String> {
+        let (towels, designs) = parse(input);
+        let report = designs
+            .filter_map(|design| towels.diagnose(design).err())
+            .map(|impossible| impossible.to_string() + "\n")
+            .collect();
+        Some(report)
+    }
+}
 
-        let mut history = HashMap::<_, _, rustc_hash::FxBuildHasher>::default();
+impl SolutionGold<usize, usize> for Day {
+    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
+    fn calculate_gold(input: &str) -> usize {
+        let (towels, designs) = parse(input);
         designs
-            .lines()
-            .filter(|design| check_can_be_made(design, &patterns, &mut history))
-            .count()
+            .map(|design| towels.count_arrangements(design) as usize)
+            .sum()
     }
 }
 
-fn check_can_be_made<'a>(
-    design: &'a str,
-    patterns: &[&str],
-    history: &mut HashMap<&'a str, bool, rustc_hash::FxBuildHasher>,
-) -> bool {
-    if design.is_empty() {
-        return true;
-    }
+/// Splits the input into the available towels and the designs to make.
+pub fn parse(input: &str) -> (Towels, impl Iterator<Item = &str>) {
+    let (patterns, designs) = input.split_once("\n\n").unwrap();
+    (Towels::new(patterns.split(", ")), designs.lines())
+}
 
-    if let Some(success) = history.get(design) {
-        return *success;
+const COLOURS: usize = 5;
+
+fn colour_index(colour: u8) -> Option<usize> {
+    match colour {
+        b'w' => Some(0),
+        b'u' => Some(1),
+        b'b' => Some(2),
+        b'r' => Some(3),
+        b'g' => Some(4),
+        _ => None,
     }
+}
 
-    let mut success = false;
-    for &pattern in patterns {
-        if pattern.len() > design.len() {
-            continue;
-        }
+/// A trie over all towel patterns, so every pattern that matches at a position can be found in a
+/// single walk.
+pub struct Towels {
+    nodes: Vec<TrieNode>,
+}
 
-        if &design[..pattern.len()] != pattern {
-            continue;
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    /// The index of the child node for every colour, where 0 means there is none.
+    children: [u32; COLOURS],
+    is_pattern: bool,
+}
+
+impl Towels {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut nodes = vec![TrieNode::default()];
+        for pattern in patterns {
+            let mut node = 0;
+            for colour in pattern.bytes() {
+                let colour = colour_index(colour).expect("unknown towel colour");
+                if nodes[node].children[colour] == 0 {
+                    nodes[node].children[colour] = nodes.len() as u32;
+                    nodes.push(TrieNode::default());
+                }
+                node = nodes[node].children[colour] as usize;
+            }
+            nodes[node].is_pattern = true;
         }
 
-        if check_can_be_made(&design[pattern.len()..], patterns, history) {
-            success = true;
-            break;
+        Towels { nodes }
+    }
+
+    /// Returns the lengths of every pattern that matches `design` at `start`, shortest first.
+    fn matches_at<'a>(
+        &'a self,
+        design: &'a [u8],
+        start: usize,
+    ) -> impl Iterator<Item = usize> + 'a {
+        design[start..]
+            .iter()
+            .scan(0, |node, &colour| {
+                *node = self.nodes[*node].children[colour_index(colour)?] as usize;
+                (*node != 0).then_some(*node)
+            })
+            .enumerate()
+            .filter(|&(_, node)| self.nodes[node].is_pattern)
+            .map(|(i, _)| i + 1)
+    }
+
+    /// Returns for every position in `design` how many ways there are to build the rest of it,
+    /// saturating at `u64::MAX`. The entry past the end is always 1.
+    pub fn arrangement_counts(&self, design: &str) -> Vec<u64> {
+        let design = design.as_bytes();
+        let mut counts = vec![0u64; design.len() + 1];
+        counts[design.len()] = 1;
+        for start in (0..design.len()).rev() {
+            counts[start] = self
+                .matches_at(design, start)
+                .fold(0, |acc, len| acc.saturating_add(counts[start + len]));
         }
+        counts
     }
 
-    history.insert(design, success);
+    /// Returns how many ways there are to build `design`, saturating at `u64::MAX`.
+    pub fn count_arrangements(&self, design: &str) -> u64 {
+        self.arrangement_counts(design)[0]
+    }
 
-    success
-}
+    /// Lazily yields every way to build `design`, as the list of patterns used from left to right.
+    pub fn arrangements<'a>(&'a self, design: &'a str) -> Arrangements<'a> {
+        Arrangements::new(self, design)
+    }
 
-impl SolutionGold<usize, usize> for Day {
-    const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
+    /// Finds a way to build `design` from as few towels as possible.
+    pub fn shortest_arrangement<'a>(&self, design: &'a str) -> Option<Vec<&'a str>> {
+        let bytes = design.as_bytes();
+
+        // for every position, the fewest towels needed for the rest and the first one to use
+        let mut fewest = vec![(usize::MAX, 0); bytes.len() + 1];
+        fewest[bytes.len()] = (0, 0);
+        for start in (0..bytes.len()).rev() {
+            fewest[start] = self
+                .matches_at(bytes, start)
+                .filter(|&len| fewest[start + len].0 != usize::MAX)
+                .map(|len| (fewest[start + len].0 + 1, len))
+                .min()
+                .unwrap_or((usize::MAX, 0));
+        }
+        if fewest[0].0 == usize::MAX {
+            return None;
+        }
 
-    fn calculate_gold(input: &str) -> usize {
-        let (patterns, designs) = input.split_once("\n\n").unwrap();
+        let mut arrangement = vec![];
+        let mut start = 0;
+        while start < bytes.len() {
+            let len = fewest[start].1;
+            arrangement.push(&design[start..start + len]);
+            start += len;
+        }
+        Some(arrangement)
+    }
 
-        let patterns = patterns.split(", ").collect::<Vec<_>>();
+    /// Returns the length of the longest prefix of `design` that can be built.
+    pub fn longest_buildable_prefix(&self, design: &str) -> usize {
+        let bytes = design.as_bytes();
+        let mut reachable = vec![false; bytes.len() + 1];
+        reachable[0] = true;
+        for start in 0..bytes.len() {
+            if reachable[start] {
+                for len in self.matches_at(bytes, start) {
+                    reachable[start + len] = true;
+                }
+            }
+        }
+        reachable.iter().rposition(|&r| r).unwrap()
+    }
 
-        let mut history = HashMap::<_, _, rustc_hash::FxBuildHasher>::default();
+    /// Checks whether `design` can be built, reporting how far it gets if it can't.
+    pub fn diagnose<'a>(&self, design: &'a str) -> Result<(), ImpossibleDesign<'a>> {
+        let buildable = self.longest_buildable_prefix(design);
+        if buildable == design.len() {
+            Ok(())
+        } else {
+            Err(ImpossibleDesign {
+                design,
+                buildable_prefix: &design[..buildable],
+            })
+        }
+    }
+}
 
-        designs
-            .lines()
-            .map(|design| check_can_be_made_gold(design, &patterns, &mut history))
-            .sum()
+/// A design that can't be built from the available towels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImpossibleDesign<'a> {
+    pub design: &'a str,
+    /// The longest part at the start of the design that can still be built.
+    pub buildable_prefix: &'a str,
+}
+
+impl Display for ImpossibleDesign<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is impossible, only {:?} ({}/{}) can be built",
+            self.design,
+            self.buildable_prefix,
+            self.buildable_prefix.len(),
+            self.design.len()
+        )
     }
 }
 
-fn check_can_be_made_gold<'a>(
+/// Iterator over every arrangement of a design, see [`Towels::arrangements`].
+pub struct Arrangements<'a> {
+    towels: &'a Towels,
     design: &'a str,
-    patterns: &[&str],
-    history: &mut HashMap<&'a str, usize, rustc_hash::FxBuildHasher>,
-) -> usize {
-    if design.is_empty() {
-        return 1;
+    counts: Vec<u64>,
+    /// For every towel placed so far, where it starts and the lengths of the towels still to try
+    /// there. Only towels after which the rest of the design can still be built are tried, so
+    /// every branch leads to at least one arrangement.
+    stack: Vec<(usize, Vec<usize>)>,
+    /// The lengths of the towels chosen so far, one less than the size of the stack.
+    chosen: Vec<usize>,
+    /// Whether the design is empty and its single, empty arrangement still has to be yielded.
+    empty_pending: bool,
+}
+
+impl<'a> Arrangements<'a> {
+    fn new(towels: &'a Towels, design: &'a str) -> Self {
+        let mut arrangements = Arrangements {
+            towels,
+            design,
+            counts: towels.arrangement_counts(design),
+            stack: vec![],
+            chosen: vec![],
+            empty_pending: design.is_empty(),
+        };
+        if !design.is_empty() && arrangements.counts[0] > 0 {
+            arrangements.stack.push((0, arrangements.viable_towels(0)));
+        }
+        arrangements
     }
 
-    if let Some(success) = history.get(design) {
-        return *success;
+    /// Returns the lengths of the towels at `start` that leave a buildable rest, in the reverse
+    /// order they should be tried in.
+    fn viable_towels(&self, start: usize) -> Vec<usize> {
+        let mut lens = self
+            .towels
+            .matches_at(self.design.as_bytes(), start)
+            .filter(|&len| self.counts[start + len] > 0)
+            .collect::<Vec<_>>();
+        lens.reverse();
+        lens
     }
+}
 
-    let mut success_count = 0;
+impl<'a> Iterator for Arrangements<'a> {
+    type Item = Vec<&'a str>;
 
-    for &pattern in patterns {
-        if pattern.len() > design.len() {
-            continue;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.empty_pending {
+            self.empty_pending = false;
+            return Some(vec![]);
         }
 
-        if &design[..pattern.len()] != pattern {
-            continue;
+        loop {
+            let (start, lens) = self.stack.last_mut()?;
+            let start = *start;
+            let Some(len) = lens.pop() else {
+                self.stack.pop();
+                self.chosen.pop();
+                continue;
+            };
+
+            if start + len == self.design.len() {
+                let mut pos = 0;
+                let arrangement = self
+                    .chosen
+                    .iter()
+                    .chain([&len])
+                    .map(|&len| {
+                        pos += len;
+                        &self.design[pos - len..pos]
+                    })
+                    .collect();
+                return Some(arrangement);
+            }
+
+            let lens = self.viable_towels(start + len);
+            self.chosen.push(len);
+            self.stack.push((start + len, lens));
         }
-
-        success_count += check_can_be_made_gold(&design[pattern.len()..], patterns, history);
     }
+}
 
-    history.insert(design, success_count);
-
-    success_count
+/// Generates an input with `pattern_count` towel patterns and `design_count` designs, most of which
+/// can be built from the patterns.
+pub fn generate_towels(pattern_count: usize, design_count: usize, seed: u64) -> String {
+    const COLOURS: &[u8] = b"wubrg";
+    let mut rng = utils::XorShift::new(seed);
+    let mut random_colours = |len: usize| {
+        (0..len)
+            .map(|_| COLOURS[rng.next_below(COLOURS.len() as u64) as usize] as char)
+            .collect::<String>()
+    };
+
+    // leave out the shortest patterns and never start one with `g`, as otherwise nearly every
+    // design would be possible
+    let mut patterns = (0..pattern_count)
+        .map(|i| {
+            let mut pattern = random_colours(3 + i % 6);
+            if pattern.starts_with('g') {
+                pattern.replace_range(..1, "b");
+            }
+            pattern
+        })
+        .collect::<Vec<_>>();
+    patterns.sort_unstable();
+    patterns.dedup();
+
+    let mut rng = utils::XorShift::new(seed.wrapping_add(1));
+    let designs = (0..design_count)
+        .map(|i| {
+            if i % 4 == 0 {
+                // the random designs that start with `g` are impossible
+                random_colours(40)
+            } else {
+                (0..8)
+                    .map(|_| patterns[rng.next_below(patterns.len() as u64) as usize].as_str())
+                    .collect()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    format!("{}\n\n{}\n", patterns.join(", "), designs.join("\n"))
 }
 
 #[test]
@@ -131,3 +356,107 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(712058625427487, output);
 }
+
+#[test]
+fn test_arrangement_counts() {
+    let (towels, _) = parse(Day::INPUT_SAMPLE);
+    // the example designs from the puzzle description, with how many ways each one can be made
+    for (design, expected) in [
+        ("brwrr", 2),
+        ("bggr", 1),
+        ("gbbr", 4),
+        ("rrbgbr", 6),
+        ("ubwu", 0),
+        ("bwurrg", 1),
+        ("brgr", 2),
+        ("bbrgwb", 0),
+    ] {
+        assert_eq!(expected, towels.count_arrangements(design), "{design}");
+    }
+    assert_eq!(vec![2, 1, 1, 1, 1, 1], towels.arrangement_counts("brwrr"));
+}
+
+#[test]
+fn test_generated_against_reference() {
+    for seed in 0..3 {
+        let input = generate_towels(3000, 100, seed);
+        let possible = Day::calculate_silver(&input);
+        assert_eq!(reference::calculate_silver(&input), possible);
+        // some of the random designs should be impossible
+        assert!((80..100).contains(&possible), "{possible}");
+        assert_eq!(
+            reference::calculate_gold(&input),
+            Day::calculate_gold(&input)
+        );
+    }
+}
+
+#[test]
+fn test_arrangements() {
+    let (towels, _) = parse(Day::INPUT_SAMPLE);
+    // the arrangements listed in the puzzle description
+    let mut arrangements = towels.arrangements("gbbr").collect::<Vec<_>>();
+    arrangements.sort();
+    assert_eq!(
+        vec![
+            vec!["g", "b", "b", "r"],
+            vec!["g", "b", "br"],
+            vec!["gb", "b", "r"],
+            vec!["gb", "br"],
+        ],
+        arrangements
+    );
+    assert_eq!(0, towels.arrangements("ubwu").count());
+    assert_eq!(
+        vec![Vec::<&str>::new()],
+        towels.arrangements("").collect::<Vec<_>>()
+    );
+
+    // the iterator yields exactly as many arrangements as were counted, each building the design
+    for design in ["brwrr", "rrbgbr", "bwurrg", "brgr"] {
+        let arrangements = towels.arrangements(design).collect::<Vec<_>>();
+        assert_eq!(towels.count_arrangements(design), arrangements.len() as u64);
+        for arrangement in arrangements {
+            assert_eq!(design, arrangement.concat());
+        }
+    }
+
+    // it is lazy, so taking a few from a design with a huge number of arrangements is fine
+    let design = "r".repeat(1000);
+    let towels = Towels::new(["r", "rr"]);
+    assert_eq!(u64::MAX, towels.count_arrangements(&design));
+    assert_eq!(5, towels.arrangements(&design).take(5).count());
+}
+
+#[test]
+fn test_shortest_arrangement() {
+    let (towels, _) = parse(Day::INPUT_SAMPLE);
+    assert_eq!(Some(vec!["gb", "br"]), towels.shortest_arrangement("gbbr"));
+    assert_eq!(
+        Some(vec!["br", "wr", "r"]),
+        towels.shortest_arrangement("brwrr")
+    );
+    assert_eq!(None, towels.shortest_arrangement("bbrgwb"));
+    assert_eq!(Some(vec![]), towels.shortest_arrangement(""));
+}
+
+#[test]
+fn test_impossible_designs() {
+    let (towels, _) = parse(Day::INPUT_SAMPLE);
+    assert_eq!(Ok(()), towels.diagnose("brgr"));
+
+    let impossible = towels.diagnose("bbrgwb").unwrap_err();
+    assert_eq!("bbrgwb", impossible.design);
+    assert_eq!("bbrg", impossible.buildable_prefix);
+    assert_eq!(
+        "bbrgwb is impossible, only \"bbrg\" (4/6) can be built",
+        impossible.to_string()
+    );
+
+    let explanation = Day::explain_silver(Day::INPUT_SAMPLE).unwrap();
+    assert_eq!(
+        "ubwu is impossible, only \"\" (0/4) can be built\n\
+         bbrgwb is impossible, only \"bbrg\" (4/6) can be built\n",
+        explanation
+    );
+}
@@ -0,0 +1,97 @@
+//! The original implementation, which tries every pattern at every suffix and memoizes by slice.
+
+use std::collections::HashMap;
+
+pub fn calculate_silver(input: &str) -> usize {
+    let (patterns, designs) = input.split_once("\n\n").unwrap();
+
+    let mut patterns = patterns.split(", ").collect::<Vec<_>>();
+
+    patterns.sort_by_key(|p| p.len());
+
+    let mut history = HashMap::<_, _, rustc_hash::FxBuildHasher>::default();
+
+    designs
+        .lines()
+        .filter(|design| check_can_be_made(design, &patterns, &mut history))
+        .count()
+}
+
+fn check_can_be_made<'a>(
+    design: &'a str,
+    patterns: &[&str],
+    history: &mut HashMap<&'a str, bool, rustc_hash::FxBuildHasher>,
+) -> bool {
+    if design.is_empty() {
+        return true;
+    }
+
+    if let Some(success) = history.get(design) {
+        return *success;
+    }
+
+    let mut success = false;
+    for &pattern in patterns {
+        if pattern.len() > design.len() {
+            continue;
+        }
+
+        if &design[..pattern.len()] != pattern {
+            continue;
+        }
+
+        if check_can_be_made(&design[pattern.len()..], patterns, history) {
+            success = true;
+            break;
+        }
+    }
+
+    history.insert(design, success);
+
+    success
+}
+
+pub fn calculate_gold(input: &str) -> usize {
+    let (patterns, designs) = input.split_once("\n\n").unwrap();
+
+    let patterns = patterns.split(", ").collect::<Vec<_>>();
+
+    let mut history = HashMap::<_, _, rustc_hash::FxBuildHasher>::default();
+
+    designs
+        .lines()
+        .map(|design| check_can_be_made_gold(design, &patterns, &mut history))
+        .sum()
+}
+
+fn check_can_be_made_gold<'a>(
+    design: &'a str,
+    patterns: &[&str],
+    history: &mut HashMap<&'a str, usize, rustc_hash::FxBuildHasher>,
+) -> usize {
+    if design.is_empty() {
+        return 1;
+    }
+
+    if let Some(success) = history.get(design) {
+        return *success;
+    }
+
+    let mut success_count = 0;
+
+    for &pattern in patterns {
+        if pattern.len() > design.len() {
+            continue;
+        }
+
+        if &design[..pattern.len()] != pattern {
+            continue;
+        }
+
+        success_count += check_can_be_made_gold(&design[pattern.len()..], patterns, history);
+    }
+
+    history.insert(design, success_count);
+
+    success_count
+}
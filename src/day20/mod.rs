@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, VecDeque};
+
 use super::*;
 
 pub struct Day;
@@ -8,79 +10,7 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let grid = input.as_bytes();
-        let width = grid.iter().position(|&c| c == b'\n').unwrap();
-        let stride = width + 1;
-        let height = (grid.len() + 1) / stride;
-
-        let start_pos = grid.iter().position(|&c| c == b'S').unwrap();
-        let end_pos = grid.iter().position(|&c| c == b'E').unwrap();
-
-        // walk through the track and get all positions
-        let mut path_lookup = vec![0; grid.len()];
-        let mut path = Vec::new();
-        path.push(start_pos);
-        let mut current_pos = start_pos;
-        let mut prev_pos = start_pos;
-        for i in 1.. {
-            if current_pos == end_pos {
-                break;
-            }
-
-            for offset in [1isize, -1, stride as isize, -(stride as isize)] {
-                let next_pos = (current_pos as isize + offset) as usize;
-                if next_pos != prev_pos && grid[next_pos] != b'#' {
-                    prev_pos = current_pos;
-                    current_pos = next_pos;
-                    path.push(current_pos);
-                    path_lookup[current_pos] = i;
-                    break;
-                }
-            }
-        }
-
-        // loop over possible cheat positions
-        let mut good_count = 0;
-        for i in 0..path.len() {
-            let pos = path[i];
-            let pos_x = pos % stride;
-            let pos_y = pos / stride;
-
-            for offset in [1isize, -1, stride as isize, -(stride as isize)] {
-                if offset == -1 && pos_x <= 1 {
-                    continue;
-                }
-                if offset == 1 && pos_x >= width - 2 {
-                    continue;
-                }
-                if offset == -(stride as isize) && pos_y <= 1 {
-                    continue;
-                }
-                if offset == stride as isize && pos_y >= height - 2 {
-                    continue;
-                }
-
-                let next_pos_1 = (pos as isize + offset) as usize;
-                let next_pos_2 = (pos as isize + offset * 2) as usize;
-
-                if !(grid[next_pos_1] == b'#' && grid[next_pos_2] != b'#') {
-                    continue;
-                }
-                let next_i = path_lookup[next_pos_2];
-
-                if next_i <= i {
-                    continue;
-                }
-
-                let skipped = (next_i - i) - 2;
-
-                if skipped >= 100 {
-                    good_count += 1;
-                }
-            }
-        }
-
-        good_count
+        Racetrack::parse(input).count_cheats(2, 100)
     }
 }
 
@@ -88,6 +18,24 @@ impl SolutionGold<usize, usize> for Day {
     const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
     fn calculate_gold(input: &str) -> usize {
+        Racetrack::parse(input).count_cheats(20, 100)
+    }
+}
+
+pub struct Racetrack {
+    width: usize,
+    height: usize,
+    stride: usize,
+    /// For every position in the grid, the shortest time to reach it from the start.
+    from_start: Vec<u32>,
+    /// For every position in the grid, the shortest time to reach the end from it.
+    to_end: Vec<u32>,
+    /// The shortest time to finish without cheating.
+    best: u32,
+}
+
+impl Racetrack {
+    pub fn parse(input: &str) -> Self {
         let grid = input.as_bytes();
         let width = grid.iter().position(|&c| c == b'\n').unwrap();
         let stride = width + 1;
@@ -96,75 +44,105 @@ impl SolutionGold<usize, usize> for Day {
         let start_pos = grid.iter().position(|&c| c == b'S').unwrap();
         let end_pos = grid.iter().position(|&c| c == b'E').unwrap();
 
-        // walk through the track and get all positions
-        let mut path_lookup = vec![0u32; grid.len()];
-        let mut path = Vec::new();
-        path.push(start_pos);
-        let mut current_pos = start_pos;
-        let mut prev_pos = start_pos;
-        for i in 1.. {
-            if current_pos == end_pos {
-                break;
-            }
+        let from_start = distances(grid, stride, start_pos);
+        let to_end = distances(grid, stride, end_pos);
+        let best = from_start[end_pos];
+
+        Racetrack {
+            width,
+            height,
+            stride,
+            from_start,
+            to_end,
+            best,
+        }
+    }
 
-            for offset in [1isize, -1, stride as isize, -(stride as isize)] {
-                let next_pos = (current_pos as isize + offset) as usize;
-                if next_pos != prev_pos && grid[next_pos] != b'#' {
-                    prev_pos = current_pos;
-                    current_pos = next_pos;
-                    path.push(current_pos);
-                    path_lookup[current_pos] = i;
-                    break;
-                }
+    /// Counts the cheats of at most `max_duration` picoseconds that save at least `min_saving`.
+    pub fn count_cheats(&self, max_duration: usize, min_saving: usize) -> usize {
+        let mut count = 0;
+        self.for_each_cheat(max_duration, |saving| {
+            if saving >= min_saving {
+                count += 1;
             }
+        });
+        count
+    }
+
+    /// Returns how many cheats of at most `max_duration` picoseconds there are for every amount of
+    /// time saved.
+    pub fn savings_histogram(&self, max_duration: usize) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        self.for_each_cheat(max_duration, |saving| {
+            *histogram.entry(saving).or_insert(0) += 1;
+        });
+        histogram
+    }
+
+    /// Calls `f` with the time saved by every cheat of at most `max_duration` picoseconds that
+    /// saves any time at all. If the end can't be reached without cheating, there are none.
+    fn for_each_cheat(&self, max_duration: usize, mut f: impl FnMut(usize)) {
+        if self.best == u32::MAX {
+            return;
         }
 
-        // loop over possible cheat positions
-        let mut good_count = 0;
-        for i in 0..path.len() {
-            let pos = path[i];
-            let pos_x = (pos % stride) as isize;
-            let pos_y = (pos / stride) as isize;
+        let max_offset = max_duration as isize;
+        for pos in 0..self.from_start.len() {
+            if self.from_start[pos] == u32::MAX {
+                continue;
+            }
+            let pos_x = (pos % self.stride) as isize;
+            let pos_y = (pos / self.stride) as isize;
 
-            // find all grid positions with a manhattan distance of 20 or less
-            for offs_y in -20isize..=20 {
+            // find all grid positions within the manhattan distance
+            for offs_y in -max_offset..=max_offset {
                 let new_y = pos_y + offs_y;
-                if new_y < 0 || new_y >= height as isize {
+                if new_y < 0 || new_y >= self.height as isize {
                     continue;
                 }
 
-                let max_x_offs = 20 - offs_y.abs();
+                let max_x_offs = max_offset - offs_y.abs();
                 for offs_x in -max_x_offs..=max_x_offs {
                     let new_x = pos_x + offs_x;
-                    if new_x < 0 || new_x >= width as isize {
+                    if new_x < 0 || new_x >= self.width as isize {
                         continue;
                     }
 
-                    // check if target position is next on the path
-                    let new_pos = (new_x + new_y * stride as isize) as usize;
-                    let next_i = path_lookup[new_pos] as usize;
-                    if next_i <= i {
+                    // check if the end can be reached from the target position
+                    let new_pos = (new_x + new_y * self.stride as isize) as usize;
+                    if self.to_end[new_pos] == u32::MAX {
                         continue;
                     }
 
-                    let manhattan_dist = (offs_x.abs() + offs_y.abs()) as usize;
-                    if next_i - i <= manhattan_dist {
-                        continue;
-                    }
-
-                    let skipped = (next_i - i) - manhattan_dist;
-
-                    debug_assert!(skipped % 2 == 0);
-
-                    if skipped >= 100 {
-                        good_count += 1;
+                    let manhattan_dist = (offs_x.abs() + offs_y.abs()) as u32;
+                    let time = self.from_start[pos] + manhattan_dist + self.to_end[new_pos];
+                    if time < self.best {
+                        f((self.best - time) as usize);
                     }
                 }
             }
         }
+    }
+}
 
-        good_count
+/// Returns the shortest time from `from` to every position on the track, or `u32::MAX` for
+/// positions that can't be reached.
+fn distances(grid: &[u8], stride: usize, from: usize) -> Vec<u32> {
+    let mut distances = vec![u32::MAX; grid.len()];
+    distances[from] = 0;
+    let mut queue = VecDeque::from([from]);
+    while let Some(pos) = queue.pop_front() {
+        for offset in [1isize, -1, stride as isize, -(stride as isize)] {
+            let next_pos = pos.wrapping_add_signed(offset);
+            if grid.get(next_pos).is_some_and(|&c| c != b'#' && c != b'\n')
+                && distances[next_pos] == u32::MAX
+            {
+                distances[next_pos] = distances[pos] + 1;
+                queue.push_back(next_pos);
+            }
+        }
     }
+    distances
 }
 
 #[test]
@@ -190,3 +168,158 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(982124, output);
 }
+
+#[test]
+fn test_savings_histogram() {
+    let track = Racetrack::parse(Day::INPUT_SAMPLE);
+
+    // the cheats listed in the puzzle description for the sample
+    let expected = BTreeMap::from([
+        (2, 14),
+        (4, 14),
+        (6, 2),
+        (8, 4),
+        (10, 2),
+        (12, 3),
+        (20, 1),
+        (36, 1),
+        (38, 1),
+        (40, 1),
+        (64, 1),
+    ]);
+    assert_eq!(expected, track.savings_histogram(2));
+
+    // only the cheats saving at least 50 picoseconds are listed for longer cheats
+    let expected = BTreeMap::from([
+        (50, 32),
+        (52, 31),
+        (54, 29),
+        (56, 39),
+        (58, 25),
+        (60, 23),
+        (62, 20),
+        (64, 19),
+        (66, 12),
+        (68, 14),
+        (70, 12),
+        (72, 22),
+        (74, 4),
+        (76, 3),
+    ]);
+    let histogram = track.savings_histogram(20).split_off(&50);
+    assert_eq!(expected, histogram);
+    assert_eq!(285, track.count_cheats(20, 50));
+}
+
+#[cfg(test)]
+fn generate_random_track(rng: &mut utils::XorShift, size: usize) -> String {
+    loop {
+        let mut grid = vec![vec![b'#'; size]; size];
+        for row in &mut grid[1..size - 1] {
+            for cell in &mut row[1..size - 1] {
+                if rng.next_below(3) != 0 {
+                    *cell = b'.';
+                }
+            }
+        }
+        let mut random_open = || loop {
+            let x = rng.next_below(size as u64 - 2) as usize + 1;
+            let y = rng.next_below(size as u64 - 2) as usize + 1;
+            if grid[y][x] == b'.' {
+                return (x, y);
+            }
+        };
+        let start = random_open();
+        let end = random_open();
+        if start == end {
+            continue;
+        }
+        grid[start.1][start.0] = b'S';
+        grid[end.1][end.0] = b'E';
+
+        let input = grid
+            .into_iter()
+            .map(|row| String::from_utf8(row).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if Racetrack::parse(&input).best != u32::MAX {
+            return input;
+        }
+    }
+}
+
+/// Tries every pair of cheat start and end positions by adding the cheat as an extra edge to the
+/// track and searching the whole track again.
+#[cfg(test)]
+fn brute_force_savings_histogram(input: &str, max_duration: usize) -> BTreeMap<usize, usize> {
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    let grid = input.as_bytes();
+    let stride = grid.iter().position(|&c| c == b'\n').unwrap() + 1;
+    let start = grid.iter().position(|&c| c == b'S').unwrap();
+    let end = grid.iter().position(|&c| c == b'E').unwrap();
+    let is_track = |pos: usize| matches!(grid[pos], b'.' | b'S' | b'E');
+
+    let shortest = |cheat: Option<(usize, usize, usize)>| {
+        let mut distances = vec![usize::MAX; grid.len()];
+        let mut pq = BinaryHeap::from([Reverse((0, start))]);
+        distances[start] = 0;
+        while let Some(Reverse((distance, pos))) = pq.pop() {
+            if pos == end {
+                return distance;
+            }
+            if distance > distances[pos] {
+                continue;
+            }
+            let mut edges = [1isize, -1, stride as isize, -(stride as isize)]
+                .map(|offset| (pos.wrapping_add_signed(offset), 1))
+                .to_vec();
+            if let Some((from, to, len)) = cheat {
+                if from == pos {
+                    edges.push((to, len));
+                }
+            }
+            for (next, len) in edges {
+                if is_track(next) && distance + len < distances[next] {
+                    distances[next] = distance + len;
+                    pq.push(Reverse((distance + len, next)));
+                }
+            }
+        }
+        usize::MAX
+    };
+
+    let best = shortest(None);
+    let mut histogram = BTreeMap::new();
+    for from in (0..grid.len()).filter(|&pos| is_track(pos)) {
+        for to in (0..grid.len()).filter(|&pos| is_track(pos)) {
+            let len = (from % stride).abs_diff(to % stride) + (from / stride).abs_diff(to / stride);
+            if len > max_duration {
+                continue;
+            }
+            let time = shortest(Some((from, to, len)));
+            if time < best {
+                *histogram.entry(best - time).or_insert(0) += 1;
+            }
+        }
+    }
+    histogram
+}
+
+#[test]
+fn test_branching_tracks_against_brute_force() {
+    let mut rng = utils::XorShift::new(20);
+    let mut found_cheats = false;
+    for _ in 0..50 {
+        let input = generate_random_track(&mut rng, 9);
+        let max_duration = rng.next_below(6) as usize + 1;
+        let histogram = Racetrack::parse(&input).savings_histogram(max_duration);
+        assert_eq!(
+            brute_force_savings_histogram(&input, max_duration),
+            histogram,
+            "{input}"
+        );
+        found_cheats |= !histogram.is_empty();
+    }
+    assert!(found_cheats);
+}
@@ -1,7 +1,9 @@
-use std::{collections::HashMap, iter};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
 
 use fnv::FnvBuildHasher;
-use utils::fast_parse_int;
 
 use super::*;
 
@@ -13,23 +15,7 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let mut cache = HashMap::<_, _, FnvBuildHasher>::default();
-        input
-            .lines()
-            .map(|line| {
-                let numeric_part = fast_parse_int(&line[..3]);
-
-                let mut all_moves = 0;
-                let mut current_char = 'A';
-                for chr in line.chars() {
-                    let new_moves = get_moves(current_char, chr, 0, 2, &mut cache);
-                    all_moves += new_moves;
-                    current_char = chr;
-                }
-
-                all_moves * numeric_part
-            })
-            .sum()
+        total_complexity(input, 2).try_into().unwrap()
     }
 }
 
@@ -37,121 +23,392 @@ impl SolutionGold<usize, usize> for Day {
     const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
     fn calculate_gold(input: &str) -> usize {
-        let mut cache = HashMap::<_, _, FnvBuildHasher>::default();
-        input
-            .lines()
-            .map(|line| {
-                let numeric_part = fast_parse_int(&line[..3]);
-
-                let mut all_moves = 0;
-                let mut current_char = 'A';
-                for chr in line.chars() {
-                    let new_moves = get_moves(current_char, chr, 0, 25, &mut cache);
-                    all_moves += new_moves;
-                    current_char = chr;
-                }
+        total_complexity(input, 25).try_into().unwrap()
+    }
+}
+
+/// Sums the complexity of every code in the input when typed through the given amount of
+/// directional keypads.
+///
+/// Codes may be of any length. Their numeric part is made up of all digits in the code.
+pub fn total_complexity(input: &str, directional_keypads: usize) -> u128 {
+    let mut chain = RobotChain::standard(directional_keypads);
+    input
+        .lines()
+        .map(|line| {
+            let numeric_part = line
+                .bytes()
+                .filter(u8::is_ascii_digit)
+                .fold(0u128, |acc, digit| {
+                    checked(
+                        acc.checked_mul(10)
+                            .and_then(|acc| acc.checked_add((digit & 0x0f) as u128)),
+                    )
+                });
+            checked(chain.sequence_length(line).checked_mul(numeric_part))
+        })
+        .fold(0, |acc, complexity| checked(acc.checked_add(complexity)))
+}
+
+/// Returns the total complexity for each amount of directional keypads in `depths`.
+pub fn sweep(input: &str, depths: impl IntoIterator<Item = usize>) -> Vec<(usize, u128)> {
+    depths
+        .into_iter()
+        .map(|depth| (depth, total_complexity(input, depth)))
+        .collect()
+}
+
+/// Unwraps the result of checked arithmetic, with a clear message when a chain gets too deep.
+fn checked(value: Option<u128>) -> u128 {
+    value.expect("result overflows a u128, the robot chain is too deep")
+}
 
-                all_moves * numeric_part
+/// The keys a robot arm can be directed with. `A` presses the key the arm is pointing at.
+const DIRECTION_KEYS: [char; 5] = ['A', '^', 'v', '<', '>'];
+
+/// A rectangular keypad layout. Any cell without a key is a gap that robot arms may never point at.
+#[derive(Debug, Clone)]
+pub struct Keypad {
+    width: usize,
+    keys: Vec<Option<char>>,
+}
+
+impl Keypad {
+    /// Creates a keypad from its rows, where a space marks a gap.
+    pub fn from_rows(rows: &[&str]) -> Self {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let keys = rows
+            .iter()
+            .flat_map(|row| {
+                let padding = width - row.len();
+                row.chars()
+                    .map(|chr| (chr != ' ').then_some(chr))
+                    .chain(std::iter::repeat_n(None, padding))
             })
-            .sum()
-    }
-}
-
-fn get_moves(
-    start_char: char,
-    target: char,
-    depth: usize,
-    max_depth: usize,
-    memoization: &mut HashMap<((i8, i8, bool), u8), usize, FnvBuildHasher>,
-) -> usize {
-    let start_pos = get_position(start_char, depth);
-    let end_pos = get_position(target, depth);
-
-    let diff_x = end_pos.0 as isize - start_pos.0 as isize;
-    let diff_y = end_pos.1 as isize - start_pos.1 as isize;
-
-    // determine which direction goes first to avoid hovering over empty space
-    let y_must_first = if depth == 0 {
-        start_pos.1 == 3 && end_pos.0 == 0
-    } else {
-        start_pos.1 == 0 && end_pos.0 == 0
-    };
-    let x_must_first = if depth == 0 {
-        end_pos.1 == 3 && start_pos.0 == 0
-    } else {
-        end_pos.1 == 0 && start_pos.0 == 0
-    };
-
-    let x_first = match (x_must_first, y_must_first) {
-        (true, false) => true,
-        (false, true) => false,
-        // moving to the left is expensive, prefer doing those first so they get done in one go
-        // in order of preference: left, down, up, right
-        // due to the layout of the keypad, down/up have the same result of `x_first == false`, so this check can be simplified
-        _ => diff_x.signum() == -1,
-    };
-
-    if depth == max_depth {
-        return diff_x.unsigned_abs() + diff_y.unsigned_abs() + 1;
-    }
-
-    if let Some(&moves) = memoization.get(&((diff_x as i8, diff_y as i8, x_first), depth as u8)) {
-        return moves;
-    }
-
-    let x_moves = iter::repeat_n(if diff_x < 0 { '<' } else { '>' }, diff_x.unsigned_abs());
-    let y_moves = iter::repeat_n(if diff_y < 0 { '^' } else { 'v' }, diff_y.unsigned_abs());
-    let all_moves = if x_first {
-        x_moves.chain(y_moves).chain(iter::once('A'))
-    } else {
-        y_moves.chain(x_moves).chain(iter::once('A'))
-    };
-
-    let mut total_len = 0;
-    let mut current_char = 'A';
-    for chr in all_moves {
-        let move_len = get_moves(current_char, chr, depth + 1, max_depth, memoization);
-        total_len += move_len;
-
-        current_char = chr;
-    }
-
-    memoization.insert(
-        ((diff_x as i8, diff_y as i8, x_first), depth as u8),
-        total_len,
-    );
+            .collect();
+
+        Self { width, keys }
+    }
+
+    /// The keypad on the door.
+    pub fn numeric() -> Self {
+        Self::from_rows(&["789", "456", "123", " 0A"])
+    }
+
+    /// The keypad used to direct a robot.
+    pub fn directional() -> Self {
+        Self::from_rows(&[" ^A", "<v>"])
+    }
+
+    fn height(&self) -> usize {
+        self.keys.len() / self.width
+    }
+
+    fn position(&self, key: char) -> Option<(usize, usize)> {
+        let index = self.keys.iter().position(|&k| k == Some(key))?;
+        Some((index % self.width, index / self.width))
+    }
+
+    fn key_at(&self, (x, y): (usize, usize)) -> Option<char> {
+        self.keys[y * self.width + x]
+    }
+
+    /// Moves from `pos` in the direction of an arrow key, if that doesn't leave the keypad or
+    /// point at a gap.
+    fn step(&self, (x, y): (usize, usize), arrow: char) -> Option<(usize, usize)> {
+        let next = match arrow {
+            '^' => (x, y.checked_sub(1)?),
+            'v' => (x, y + 1),
+            '<' => (x.checked_sub(1)?, y),
+            '>' => (x + 1, y),
+            chr => unreachable!("Invalid direction: {chr}"),
+        };
+
+        (next.0 < self.width && next.1 < self.height() && self.key_at(next).is_some())
+            .then_some(next)
+    }
+}
+
+/// A chain of robots, each typing on its own keypad. The robot on the first keypad is directed by
+/// the robot on the second, and so on, with the human pressing the keys for the last robot.
+pub struct RobotChain {
+    keypads: Vec<Keypad>,
+    /// The cost and cheapest path for each (layer, from, to) combination.
+    memoization: HashMap<(u8, char, char), (u128, Vec<char>), FnvBuildHasher>,
+}
+
+impl RobotChain {
+    /// Creates a chain from its keypads, starting at the one the code is typed on. Every keypad
+    /// except the first is operated by a robot, so must contain all direction keys.
+    pub fn new(keypads: Vec<Keypad>) -> Self {
+        assert!(
+            !keypads.is_empty(),
+            "a robot chain needs at least one keypad"
+        );
+        assert!(
+            keypads[1..].iter().all(|keypad| DIRECTION_KEYS
+                .iter()
+                .all(|&key| keypad.position(key).is_some())),
+            "keypads after the first must contain all direction keys"
+        );
+
+        Self {
+            keypads,
+            memoization: HashMap::default(),
+        }
+    }
+
+    /// The setup from the puzzle: a numeric keypad and the given amount of directional keypads.
+    pub fn standard(directional_keypads: usize) -> Self {
+        let mut keypads = vec![Keypad::numeric()];
+        keypads.extend(std::iter::repeat_n(
+            Keypad::directional(),
+            directional_keypads,
+        ));
+        Self::new(keypads)
+    }
+
+    /// Returns the amount of buttons the human needs to press to type `code` on the first keypad.
+    pub fn sequence_length(&mut self, code: &str) -> u128 {
+        let mut all_moves = 0u128;
+        let mut current_char = 'A';
+        for chr in code.chars() {
+            all_moves = checked(all_moves.checked_add(self.press_cost(0, current_char, chr)));
+            current_char = chr;
+        }
+        all_moves
+    }
+
+    /// Lazily yields a shortest sequence of keys pressed on the keypad at `layer` to type `code` on
+    /// the first keypad. Layer 0 is the code itself, and a layer equal to the amount of keypads
+    /// yields the keys the human presses.
+    ///
+    /// With many robots this sequence gets astronomically long, so it is never fully stored.
+    pub fn sequence<'a>(&'a mut self, code: &'a str, layer: usize) -> Sequence<'a> {
+        assert!(layer <= self.keypads.len(), "layer {layer} is out of range");
+
+        // this fills the memoization table with the paths needed to expand the sequence
+        self.sequence_length(code);
+
+        Sequence {
+            chain: self,
+            layer,
+            code: code.chars(),
+            code_prev: 'A',
+            stack: vec![],
+        }
+    }
+
+    /// Simulates the human typing `sequence`, checking that it types exactly `code` on the first
+    /// keypad without any robot arm pointing at a gap.
+    pub fn verify(
+        &self,
+        code: &str,
+        sequence: impl IntoIterator<Item = char>,
+    ) -> Result<(), SimulationError> {
+        let mut arms = self.start_arms();
+        let mut expected = code.chars();
+        let mut typed = 0;
+
+        for (press, key) in sequence.into_iter().enumerate() {
+            let Some(typed_key) = self
+                .press(&mut arms, key)
+                .map_err(|layer| SimulationError::PointsAtGap { press, layer })?
+            else {
+                continue;
+            };
+
+            match expected.next() {
+                Some(expected) if expected == typed_key => typed += 1,
+                expected => {
+                    return Err(SimulationError::WrongKey {
+                        press,
+                        expected,
+                        typed: typed_key,
+                    })
+                }
+            }
+        }
+
+        match expected.next() {
+            None => Ok(()),
+            Some(_) => Err(SimulationError::Incomplete { typed }),
+        }
+    }
+
+    /// The position of every robot arm at the start: pointing at `A`.
+    fn start_arms(&self) -> Vec<(usize, usize)> {
+        self.keypads
+            .iter()
+            .map(|keypad| keypad.position('A').expect("keypad should contain A"))
+            .collect()
+    }
+
+    /// Simulates the human pressing `key`, returning the key typed on the first keypad if any.
+    /// If a robot arm would point at a gap, returns the layer of that arm instead.
+    fn press(&self, arms: &mut [(usize, usize)], key: char) -> Result<Option<char>, usize> {
+        let mut key = key;
+        for layer in (0..self.keypads.len()).rev() {
+            if key != 'A' {
+                arms[layer] = self.keypads[layer].step(arms[layer], key).ok_or(layer)?;
+                return Ok(None);
+            }
+
+            key = self.keypads[layer].key_at(arms[layer]).unwrap();
+        }
+
+        Ok(Some(key))
+    }
+
+    /// Returns the amount of human button presses needed to move the arm at `layer` from `from` to
+    /// `to` and press it, assuming all arms above it start at and return to `A`.
+    fn press_cost(&mut self, layer: usize, from: char, to: char) -> u128 {
+        // the human can press any key directly
+        if layer == self.keypads.len() {
+            return 1;
+        }
+
+        if let Some(&(cost, _)) = self.memoization.get(&(layer as u8, from, to)) {
+            return cost;
+        }
+
+        let (cost, path) = self.cheapest_path(layer, from, to);
+        self.memoization
+            .insert((layer as u8, from, to), (cost, path));
+        cost
+    }
+
+    /// Finds the cheapest sequence of direction keys that moves the arm at `layer` from `from` to
+    /// `to` and presses it.
+    ///
+    /// Which path is cheapest depends on the layers above, so this runs Dijkstra over
+    /// (position, last pressed direction key) using their costs. This avoids gaps anywhere in the
+    /// layout, even if they force a detour.
+    fn cheapest_path(&mut self, layer: usize, from: char, to: char) -> (u128, Vec<char>) {
+        let mut costs_above = [[0; DIRECTION_KEYS.len()]; DIRECTION_KEYS.len()];
+        for (i, &prev) in DIRECTION_KEYS.iter().enumerate() {
+            for (j, &next) in DIRECTION_KEYS.iter().enumerate() {
+                costs_above[i][j] = self.press_cost(layer + 1, prev, next);
+            }
+        }
+
+        let keypad = &self.keypads[layer];
+        let start_pos = keypad
+            .position(from)
+            .unwrap_or_else(|| panic!("Invalid char: {from}"));
+        let end_pos = keypad
+            .position(to)
+            .unwrap_or_else(|| panic!("Invalid char: {to}"));
+
+        // the index of the last pressed direction key is part of the state
+        let state_index = |(x, y): (usize, usize), last: usize| {
+            (y * keypad.width + x) * DIRECTION_KEYS.len() + last
+        };
+        let mut best = vec![u128::MAX; keypad.keys.len() * DIRECTION_KEYS.len()];
+        let mut previous = vec![None; best.len()];
+
+        let mut pq = BinaryHeap::new();
+        best[state_index(start_pos, 0)] = 0;
+        pq.push(Reverse((0, start_pos, 0)));
+
+        let mut finish = None::<(u128, (usize, usize), usize)>;
+        while let Some(Reverse((cost, pos, last))) = pq.pop() {
+            if cost > best[state_index(pos, last)] {
+                continue;
+            }
 
-    total_len
-}
-
-fn get_position(input: char, depth: usize) -> (usize, usize) {
-    if depth == 0 {
-        match input {
-            '7' => (0, 0),
-            '8' => (1, 0),
-            '9' => (2, 0),
-            '4' => (0, 1),
-            '5' => (1, 1),
-            '6' => (2, 1),
-            '1' => (0, 2),
-            '2' => (1, 2),
-            '3' => (2, 2),
-            '0' => (1, 3),
-            'A' => (2, 3),
-            chr => unreachable!("Invalid char: {chr}"),
+            if pos == end_pos {
+                let total = checked(cost.checked_add(costs_above[last][0]));
+                if finish.is_none_or(|(best_total, _, _)| total < best_total) {
+                    finish = Some((total, pos, last));
+                }
+            }
+
+            for (arrow_index, &arrow) in DIRECTION_KEYS.iter().enumerate().skip(1) {
+                let Some(next_pos) = keypad.step(pos, arrow) else {
+                    continue;
+                };
+
+                let next_cost = checked(cost.checked_add(costs_above[last][arrow_index]));
+                let next_index = state_index(next_pos, arrow_index);
+                if next_cost < best[next_index] {
+                    best[next_index] = next_cost;
+                    previous[next_index] = Some((pos, last));
+                    pq.push(Reverse((next_cost, next_pos, arrow_index)));
+                }
+            }
         }
-    } else {
-        match input {
-            '^' => (1, 0),
-            'A' => (2, 0),
-            '<' => (0, 1),
-            'v' => (1, 1),
-            '>' => (2, 1),
-            chr => unreachable!("Invalid char: {chr}"),
+
+        let (total, mut pos, mut last) = finish.expect("target key should be reachable");
+
+        let mut arrows = vec!['A'];
+        while let Some((prev_pos, prev_last)) = previous[state_index(pos, last)] {
+            arrows.push(DIRECTION_KEYS[last]);
+            (pos, last) = (prev_pos, prev_last);
         }
+        arrows.reverse();
+
+        (total, arrows)
     }
 }
 
+/// A lazily expanded key sequence, created by [`RobotChain::sequence`].
+pub struct Sequence<'a> {
+    chain: &'a RobotChain,
+    layer: usize,
+    code: std::str::Chars<'a>,
+    code_prev: char,
+    /// For every layer above 0 that is being expanded, the remaining keys of the current path and
+    /// the last key that was pressed.
+    stack: Vec<(std::slice::Iter<'a, char>, char)>,
+}
+
+impl Iterator for Sequence<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            // keys taken from the top of the stack are pressed on this layer
+            let layer = self.stack.len();
+            let (prev, key) = match self.stack.last_mut() {
+                Some((path, prev)) => {
+                    let Some(&key) = path.next() else {
+                        self.stack.pop();
+                        continue;
+                    };
+                    (std::mem::replace(prev, key), key)
+                }
+                None => {
+                    let key = self.code.next()?;
+                    (std::mem::replace(&mut self.code_prev, key), key)
+                }
+            };
+
+            if layer == self.layer {
+                return Some(key);
+            }
+
+            // every path ends by pressing `A`, so the arm above starts there again
+            let (_, path) = &self.chain.memoization[&(layer as u8, prev, key)];
+            self.stack.push((path.iter(), 'A'));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationError {
+    /// The robot arm at `layer` would point at a gap after the `press`-th key.
+    PointsAtGap { press: usize, layer: usize },
+    /// The `press`-th key typed a key on the first keypad that isn't part of the code.
+    WrongKey {
+        press: usize,
+        expected: Option<char>,
+        typed: char,
+    },
+    /// The sequence ended after typing only part of the code.
+    Incomplete { typed: usize },
+}
+
 #[test]
 fn test_silver_sample() {
     let output = Day::calculate_silver(Day::INPUT_SAMPLE);
@@ -176,3 +433,174 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(306335137543664, output);
 }
+
+#[test]
+fn test_custom_keypads() {
+    // finds the shortest sequence by simulating every possible human button press
+    fn brute_force(chain: &RobotChain, code: &str) -> u128 {
+        let code = code.chars().collect::<Vec<_>>();
+
+        let mut seen = std::collections::HashSet::new();
+        let mut queue = std::collections::VecDeque::from([(chain.start_arms(), 0, 0)]);
+        while let Some((arms, typed, presses)) = queue.pop_front() {
+            if typed == code.len() {
+                return presses;
+            }
+            if !seen.insert((arms.clone(), typed)) {
+                continue;
+            }
+
+            for key in DIRECTION_KEYS {
+                let mut arms = arms.clone();
+                match chain.press(&mut arms, key) {
+                    Ok(None) => queue.push_back((arms, typed, presses + 1)),
+                    Ok(Some(chr)) if chr == code[typed] => {
+                        queue.push_back((arms, typed + 1, presses + 1))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        unreachable!()
+    }
+
+    let layouts = [
+        vec![Keypad::numeric(), Keypad::directional()],
+        vec![
+            Keypad::numeric(),
+            Keypad::directional(),
+            Keypad::directional(),
+        ],
+        vec![
+            Keypad::from_rows(&["789", "4 6", "123", "50A"]),
+            Keypad::directional(),
+        ],
+        vec![
+            Keypad::numeric(),
+            Keypad::from_rows(&["<v>", " ^A"]),
+            Keypad::from_rows(&["A^v", "<> "]),
+        ],
+        vec![
+            Keypad::from_rows(&["12 ", "3 4", "A56"]),
+            Keypad::from_rows(&["^> ", "A<v"]),
+        ],
+    ];
+
+    for keypads in layouts {
+        let mut chain = RobotChain::new(keypads.clone());
+        for code in ["029A", "980A", "456A", "1A", "65A", "4A"] {
+            if code.chars().any(|chr| keypads[0].position(chr).is_none()) {
+                continue;
+            }
+
+            assert_eq!(
+                brute_force(&chain, code),
+                chain.sequence_length(code),
+                "{code} on {keypads:?}"
+            );
+
+            let length = chain.sequence_length(code);
+            let sequence = chain.sequence(code, keypads.len()).collect::<String>();
+            assert_eq!(length, sequence.len() as u128);
+            assert_eq!(Ok(()), chain.verify(code, sequence.chars()));
+        }
+    }
+}
+
+#[test]
+fn test_sequence() {
+    let mut chain = RobotChain::standard(2);
+
+    assert_eq!("029A", chain.sequence("029A", 0).collect::<String>());
+
+    // the puzzle lists `<A^A>^^AvvvA`, `<A^A^>^AvvvA` and `<A^A^^>AvvvA` as the shortest options
+    let first_robot = chain.sequence("029A", 1).collect::<String>();
+    assert_eq!(12, first_robot.len());
+    assert!(first_robot.starts_with("<A^A") && first_robot.ends_with("AvvvA"));
+
+    assert_eq!(28, chain.sequence("029A", 2).count());
+
+    let human = chain.sequence("029A", 3).collect::<String>();
+    assert_eq!(68, human.len());
+    assert_eq!(Ok(()), chain.verify("029A", human.chars()));
+    assert_eq!(
+        Ok(()),
+        chain.verify(
+            "029A",
+            "<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A".chars()
+        )
+    );
+
+    let mut chain = RobotChain::standard(10);
+    let length = chain.sequence_length("029A");
+    assert_eq!(length, chain.sequence("029A", 11).count() as u128);
+    let human = chain.sequence("029A", 11).collect::<Vec<_>>();
+    assert_eq!(Ok(()), chain.verify("029A", human));
+
+    // too long to expand fully, but any prefix should be valid
+    let mut chain = RobotChain::standard(25);
+    let prefix = chain.sequence("029A", 26).take(100_000).collect::<Vec<_>>();
+    assert_eq!(
+        Err(SimulationError::Incomplete { typed: 0 }),
+        chain.verify("029A", prefix)
+    );
+}
+
+#[test]
+fn test_verify_errors() {
+    let chain = RobotChain::standard(0);
+    assert_eq!(Ok(()), chain.verify("029A", "<A^A^^>AvvvA".chars()));
+    assert_eq!(
+        Err(SimulationError::PointsAtGap { press: 1, layer: 0 }),
+        chain.verify("029A", "<<".chars())
+    );
+    assert_eq!(
+        Err(SimulationError::WrongKey {
+            press: 0,
+            expected: Some('0'),
+            typed: 'A'
+        }),
+        chain.verify("029A", "A".chars())
+    );
+    assert_eq!(
+        Err(SimulationError::Incomplete { typed: 2 }),
+        chain.verify("029A", "<A^A".chars())
+    );
+
+    let chain = RobotChain::standard(1);
+    assert_eq!(
+        Err(SimulationError::PointsAtGap { press: 1, layer: 1 }),
+        chain.verify("029A", "<<".chars())
+    );
+}
+
+#[test]
+fn test_total_complexity() {
+    assert_eq!(126384, total_complexity(Day::INPUT_SAMPLE, 2));
+
+    // codes of any length, with the numeric part taken from all digits
+    let mut chain = RobotChain::standard(2);
+    let expected = chain.sequence_length("03A") * 3
+        + chain.sequence_length("7A") * 7
+        + chain.sequence_length("12345A") * 12345;
+    assert_eq!(expected, total_complexity("03A\n7A\n12345A", 2));
+
+    // deep chains no longer fit in a u64
+    assert!(total_complexity(Day::INPUT_REAL, 60) > u64::MAX as u128);
+}
+
+#[test]
+fn test_sweep() {
+    let complexities = sweep(Day::INPUT_SAMPLE, 0..=25);
+    assert_eq!(26, complexities.len());
+    assert_eq!((2, 126384), complexities[2]);
+    assert_eq!((25, 154115708116294), complexities[25]);
+    assert!(complexities.windows(2).all(|w| w[0].1 < w[1].1));
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn test_total_complexity_overflow() {
+    total_complexity(Day::INPUT_REAL, 200);
+}
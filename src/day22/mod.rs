@@ -13,19 +13,7 @@ impl SolutionSilver<usize> for Day {
         input
             .lines()
             .map(fast_parse_int)
-            .map(|num| {
-                let mut num = num;
-                for _ in 0..2000 {
-                    num = num ^ (num * 64);
-                    num %= 16777216;
-                    num = num ^ (num / 32);
-                    num %= 16777216;
-                    num = num ^ (num * 2048);
-                    num %= 16777216;
-                }
-
-                num
-            })
+            .map(|num| SecretRng::new(num as u32).nth(1999).unwrap() as usize)
             .sum()
     }
 }
@@ -34,64 +22,150 @@ impl SolutionGold<usize, usize> for Day {
     const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
     fn calculate_gold(input: &str) -> usize {
-        let mut highest_found = 0;
-
-        let hashes_list = input
-            .lines()
-            .map(fast_parse_int)
-            .map(|mut num| {
-                let mut acc: Vec<(usize, isize)> = vec![];
-                let mut prev_num = 0;
-                (0..2000).for_each(|_| {
-                    num = num ^ (num * 64);
-                    num %= 16777216;
-                    num = num ^ (num / 32);
-                    num %= 16777216;
-                    num = num ^ (num * 2048);
-                    num %= 16777216;
-
-                    acc.push((num, (num % 10) as isize - (prev_num % 10) as isize));
-                    prev_num = num;
-                });
-                acc[0].1 = -10; // ensure first does not match
-                acc
-            })
-            .collect::<Vec<_>>();
-
-        for n0 in -9..=9 {
-            dbg!(n0);
-            for n1 in -9..=9 {
-                for n2 in -9..=9 {
-                    for n3 in -9..=9 {
-                        let changes: [isize; 4] = [n0, n1, n2, n3];
-
-                        let mut sum = 0;
-                        for hashes in hashes_list.iter() {
-                            let idx = hashes.windows(4).position(|window| {
-                                window[0].1 == changes[0]
-                                    && window[1].1 == changes[1]
-                                    && window[2].1 == changes[2]
-                                    && window[3].1 == changes[3]
-                            });
-
-                            if let Some(idx) = idx {
-                                sum += hashes[idx + 3].0 % 10;
-                            }
-                        }
-
-                        if sum > highest_found {
-                            println!("Found: {} with {:?}", sum, changes);
-                        }
-                        highest_found = highest_found.max(sum);
-                    }
+        // each window of 4 changes in -9..=9 is encoded as a base-19 number
+        const SEQUENCE_COUNT: usize = 19 * 19 * 19 * 19;
+
+        let mut prices_per_sequence = vec![0u32; SEQUENCE_COUNT];
+        let mut seen = vec![0u64; SEQUENCE_COUNT.div_ceil(64)];
+
+        input.lines().map(fast_parse_int).for_each(|num| {
+            seen.fill(0);
+
+            let mut sequence = 0;
+            let mut prev_price = num % 10;
+            for (i, num) in SecretRng::new(num as u32).take(2000).enumerate() {
+                let price = num as usize % 10;
+                // shift in the new change, dropping the oldest one
+                sequence = (sequence * 19 + (price + 9 - prev_price)) % SEQUENCE_COUNT;
+                prev_price = price;
+
+                // only the first occurrence of a sequence is sold at
+                if i >= 3 && seen[sequence / 64] & (1 << (sequence % 64)) == 0 {
+                    seen[sequence / 64] |= 1 << (sequence % 64);
+                    prices_per_sequence[sequence] += price as u32;
                 }
             }
+        });
+
+        prices_per_sequence.into_iter().max().unwrap() as usize
+    }
+}
+
+const SECRET_MASK: u32 = 16777216 - 1;
+
+/// Generates the secret numbers of a single buyer.
+///
+/// Every step is a linear map over GF(2)^24, so [`Iterator::nth`] can skip ahead using
+/// precomputed powers of that map instead of evolving the secret one step at a time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SecretRng {
+    secret: u32,
+}
+
+impl SecretRng {
+    pub fn new(secret: u32) -> Self {
+        debug_assert!(secret <= SECRET_MASK);
+        Self { secret }
+    }
+
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+}
+
+impl Iterator for SecretRng {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.secret = evolve(self.secret);
+        Some(self.secret)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<u32> {
+        let steps = n as u64 + 1;
+        self.secret = (0..64)
+            .filter(|bit| steps & (1 << bit) != 0)
+            .fold(self.secret, |secret, bit| apply(&JUMP_TABLE[bit], secret));
+        Some(self.secret)
+    }
+}
+
+#[inline(always)]
+const fn evolve(mut num: u32) -> u32 {
+    // mixing and pruning, without the multiplications and divisions
+    num ^= (num << 6) & SECRET_MASK;
+    num ^= num >> 5;
+    num ^= (num << 11) & SECRET_MASK;
+    num
+}
+
+/// Evolves many secrets in lockstep by the given amount of steps.
+///
+/// Secrets are processed in fixed-size chunks so the compiler can turn the inner loop into SIMD
+/// instructions.
+pub fn evolve_batch(secrets: &mut [u32], steps: usize) {
+    const LANES: usize = 16;
+
+    let mut chunks = secrets.chunks_exact_mut(LANES);
+    for chunk in &mut chunks {
+        let mut lanes: [u32; LANES] = (&*chunk).try_into().unwrap();
+        for _ in 0..steps {
+            lanes.iter_mut().for_each(|lane| *lane = evolve(*lane));
         }
+        chunk.copy_from_slice(&lanes);
+    }
 
-        highest_found
+    for secret in chunks.into_remainder() {
+        for _ in 0..steps {
+            *secret = evolve(*secret);
+        }
     }
 }
 
+/// A 24x24 matrix over GF(2), stored as its columns.
+type Matrix = [u32; 24];
+
+/// `JUMP_TABLE[k]` evolves a secret by `2^k` steps.
+const JUMP_TABLE: [Matrix; 64] = {
+    let mut table = [[0; 24]; 64];
+
+    let mut i = 0;
+    while i < 24 {
+        table[0][i] = evolve(1 << i);
+        i += 1;
+    }
+
+    let mut k = 1;
+    while k < 64 {
+        table[k] = compose(&table[k - 1], &table[k - 1]);
+        k += 1;
+    }
+
+    table
+};
+
+const fn apply(matrix: &Matrix, vector: u32) -> u32 {
+    let mut output = 0;
+    let mut i = 0;
+    while i < 24 {
+        // branchless version of `if vector & (1 << i) != 0 { output ^= matrix[i] }`
+        output ^= matrix[i] & 0u32.wrapping_sub((vector >> i) & 1);
+        i += 1;
+    }
+    output
+}
+
+/// Returns the matrix that applies `right` and then `left`.
+const fn compose(left: &Matrix, right: &Matrix) -> Matrix {
+    let mut output = [0; 24];
+    let mut i = 0;
+    while i < 24 {
+        output[i] = apply(left, right[i]);
+        i += 1;
+    }
+    output
+}
+
 #[test]
 fn test_silver_sample() {
     let output = Day::calculate_silver(Day::INPUT_SAMPLE);
@@ -110,9 +184,46 @@ fn test_gold_sample() {
     assert_eq!(23, output);
 }
 
-// #[test]
-#[allow(unused)]
+#[test]
 fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!(2218, output);
 }
+
+#[test]
+fn test_secret_rng() {
+    let secrets = SecretRng::new(123).take(10).collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+            5908254
+        ],
+        secrets
+    );
+}
+
+#[test]
+fn test_secret_rng_jump_ahead() {
+    for seed in [1, 123, 2024, 16777215] {
+        for n in [0, 1, 2, 9, 1999, 4096, 123456] {
+            let mut stepped = SecretRng::new(seed);
+            for _ in 0..n {
+                stepped.next();
+            }
+            let mut jumped = SecretRng::new(seed);
+            assert_eq!(stepped.next(), jumped.nth(n), "seed {seed}, n {n}");
+            assert_eq!(stepped, jumped);
+        }
+    }
+}
+
+#[test]
+fn test_secret_rng_batch() {
+    let seeds = (0..100).map(|i| i * 12345 % 16777216).collect::<Vec<_>>();
+    let mut batch = seeds.clone();
+    evolve_batch(&mut batch, 2000);
+
+    for (seed, secret) in seeds.into_iter().zip(batch) {
+        assert_eq!(SecretRng::new(seed).nth(1999), Some(secret));
+    }
+}
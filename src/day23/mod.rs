@@ -2,8 +2,7 @@ use core::str;
 use std::collections::HashMap;
 
 use fnv::FnvBuildHasher;
-use petgraph::graph::NodeIndex;
-use tinyvec::TinyVec;
+use petgraph::graph::{NodeIndex, UnGraph};
 
 use super::*;
 
@@ -15,47 +14,9 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let mut graph = petgraph::graph::UnGraph::<u16, ()>::new_undirected();
-        let mut node_lookup = HashMap::<_, _, FnvBuildHasher>::default();
-        let input = input.as_bytes();
-        let line_count = (input.len() + 1) / 6;
-        (0..line_count).for_each(|i| {
-            let n1 = u16::from_ne_bytes(input[i * 6..][..2].try_into().unwrap());
-            let n2 = u16::from_ne_bytes(input[i * 6 + 3..][..2].try_into().unwrap());
-
-            let node1_key = *node_lookup.entry(n1).or_insert_with(|| graph.add_node(n1));
-            let node2_key = *node_lookup.entry(n2).or_insert_with(|| graph.add_node(n2));
-
-            graph.add_edge(node1_key, node2_key, ());
-        });
-
-        graph
-            .node_indices()
-            .map(|n| {
-                let all_neighbours = &graph
-                    .neighbors_undirected(n)
-                    .filter(|&neighbour| neighbour.index() > n.index())
-                    .collect::<TinyVec<[_; 16]>>();
-
-                if all_neighbours.len() < 2 {
-                    return 0;
-                }
-
-                let start_is_t = u16::to_ne_bytes(*graph.node_weight(n).unwrap())[0] == b't';
+        let (graph, _) = parse_graph(input);
 
-                // get all combinations of 2
-                (0..all_neighbours.len())
-                    .flat_map(move |i| (i..all_neighbours.len()).map(move |j| (i, j)))
-                    .map(|(i, j)| [n, all_neighbours[i], all_neighbours[j]])
-                    .filter(|a| {
-                        start_is_t
-                            || u16::to_ne_bytes(*graph.node_weight(a[1]).unwrap())[0] == b't'
-                            || u16::to_ne_bytes(*graph.node_weight(a[2]).unwrap())[0] == b't'
-                    })
-                    .filter(|a| graph.contains_edge(a[1], a[2]))
-                    .count()
-            })
-            .sum()
+        count_k_cliques(&graph, 3, 1, |&name| starts_with_t(name))
     }
 }
 
@@ -63,73 +24,18 @@ impl SolutionGold<usize, String> for Day {
     const INPUT_SAMPLE_GOLD: &'static str = include_str!("input_sample_gold.txt");
 
     fn calculate_gold(input: &str) -> String {
-        let mut graph = petgraph::graph::UnGraph::<u16, ()>::new_undirected();
-        let mut node_lookup = HashMap::<_, _, FnvBuildHasher>::default();
-        let input = input.as_bytes();
-        let line_count = (input.len() + 1) / 6;
-        (0..line_count).for_each(|i| {
-            let n1 = u16::from_ne_bytes(input[i * 6..][..2].try_into().unwrap());
-            let n2 = u16::from_ne_bytes(input[i * 6 + 3..][..2].try_into().unwrap());
-
-            let node1_key = *node_lookup.entry(n1).or_insert_with(|| graph.add_node(n1));
-            let node2_key = *node_lookup.entry(n2).or_insert_with(|| graph.add_node(n2));
-
-            graph.add_edge(node1_key, node2_key, ());
-        });
-
-        let mut overal_largest_group = None::<TinyVec<[NodeIndex; 16]>>;
-        graph.node_indices().for_each(|leader_idx| {
-            // assign the lowest value in a group to be the "leader" to prevent duplicate work
-            // only look at nodes that have a higher value than the leader
-            let leader_val = *graph.node_weight(leader_idx).unwrap();
-            let neighbours = graph
-                .neighbors_undirected(leader_idx)
-                .filter(|&neighbour| graph.node_weight(neighbour).unwrap() > &leader_val)
-                .collect::<TinyVec<[_; 16]>>();
-
-            // for each group size, take a combination of neighbours and see if they are all connected
-            // part 1 defined groups of size 3, so we can start from 2 required neighbours
-            let min_size = overal_largest_group.as_ref().map(|g| g.len()).unwrap_or(2);
-            let largest_group = (min_size..neighbours.len())
-                .rev()
-                .flat_map(|neighbour_count| {
-                    // very hacky way of getting all combinations of N elements
-                    (((1u32 << neighbour_count) - 1)..1 << (neighbour_count + 1))
-                        .filter(|&num| num.count_ones() == neighbour_count as u32)
-                        .map(|bitmap| {
-                            (0..neighbours.len())
-                                .filter(|i| bitmap & (1 << i) != 0)
-                                .map(|i| neighbours[i])
-                                .collect::<TinyVec<[_; 16]>>()
-                        })
-                        .filter(|group| {
-                            // every item in the group must connect to all other items in the group
-                            (0..group.len() - 1).all(|i| {
-                                (i + 1..group.len())
-                                    .all(|j| graph.contains_edge(group[i], group[j]))
-                            })
-                        })
-                        .max_by_key(|group| group.len())
-                })
-                .next();
-
-            if let Some(mut largest_group) = largest_group {
-                if overal_largest_group.is_none()
-                    || (overal_largest_group.is_some()
-                        && overal_largest_group.as_ref().unwrap().len() < largest_group.len() + 1)
-                {
-                    largest_group.push(leader_idx);
-                    overal_largest_group = Some(largest_group);
-                }
-            }
-        });
+        let (graph, _) = parse_graph(input);
+
+        let largest_group = maximal_cliques(&graph)
+            .into_iter()
+            .max_by_key(|clique| clique.len())
+            .unwrap();
 
-        let mut vals = overal_largest_group
-            .unwrap()
+        let mut vals = largest_group
             .into_iter()
             .map(|idx| graph.node_weight(idx).unwrap())
             .map(|num| String::from_utf8_lossy(&num.to_ne_bytes()).to_string())
-            .collect::<TinyVec<[_; 16]>>();
+            .collect::<Vec<_>>();
 
         vals.sort_unstable();
 
@@ -137,6 +43,251 @@ impl SolutionGold<usize, String> for Day {
     }
 }
 
+/// Parses the `aa-bb` edge list into a graph where each node holds its 2-letter name packed in a
+/// `u16`, along with a lookup from packed names to their nodes.
+pub fn parse_graph(input: &str) -> (UnGraph<u16, ()>, HashMap<u16, NodeIndex, FnvBuildHasher>) {
+    let mut graph = UnGraph::<u16, ()>::new_undirected();
+    let mut node_lookup = HashMap::<_, _, FnvBuildHasher>::default();
+    let input = input.as_bytes();
+    let line_count = (input.len() + 1) / 6;
+    (0..line_count).for_each(|i| {
+        let n1 = u16::from_ne_bytes(input[i * 6..][..2].try_into().unwrap());
+        let n2 = u16::from_ne_bytes(input[i * 6 + 3..][..2].try_into().unwrap());
+
+        let node1_key = *node_lookup.entry(n1).or_insert_with(|| graph.add_node(n1));
+        let node2_key = *node_lookup.entry(n2).or_insert_with(|| graph.add_node(n2));
+
+        graph.add_edge(node1_key, node2_key, ());
+    });
+
+    (graph, node_lookup)
+}
+
+/// Packs a 2-letter computer name the same way [`parse_graph`] does, to look it up in the graph.
+pub fn pack_name(name: &str) -> u16 {
+    u16::from_ne_bytes(name.as_bytes().try_into().unwrap())
+}
+
+/// Checks if a packed node name starts with the letter `t`.
+pub fn starts_with_t(name: u16) -> bool {
+    name.to_ne_bytes()[0] == b't'
+}
+
+/// Calls `f` for every clique of exactly `k` nodes where at least `min_matching` nodes match
+/// `predicate`. Each clique is visited once, with its nodes in ascending index order.
+///
+/// For example, `for_each_k_clique(&graph, 4, 2, |&n| starts_with_t(n), f)` lists the groups of 4
+/// interconnected computers that contain at least 2 computers starting with `t`.
+pub fn for_each_k_clique<N, E>(
+    graph: &UnGraph<N, E>,
+    k: usize,
+    min_matching: usize,
+    predicate: impl Fn(&N) -> bool,
+    mut f: impl FnMut(&[NodeIndex]),
+) {
+    if k == 0 || min_matching > k {
+        return;
+    }
+
+    let forward_adjacency = adjacency(graph, true);
+    let mut all_nodes = BitSet::new(graph.node_count());
+    (0..graph.node_count()).for_each(|i| all_nodes.insert(i));
+
+    let matches = graph
+        .node_indices()
+        .map(|n| predicate(graph.node_weight(n).unwrap()))
+        .collect::<Vec<_>>();
+
+    extend_k_clique(
+        &forward_adjacency,
+        k,
+        &mut vec![],
+        &all_nodes,
+        &mut |clique| {
+            let matching = clique.iter().filter(|n| matches[n.index()]).count();
+            // stop extending cliques that can no longer reach the required amount of matches
+            if matching + (k - clique.len()) < min_matching {
+                return false;
+            }
+            if clique.len() == k {
+                f(clique);
+            }
+            true
+        },
+    );
+}
+
+/// Counts the cliques of exactly `k` nodes where at least `min_matching` nodes match `predicate`.
+pub fn count_k_cliques<N, E>(
+    graph: &UnGraph<N, E>,
+    k: usize,
+    min_matching: usize,
+    predicate: impl Fn(&N) -> bool,
+) -> usize {
+    let mut count = 0;
+    for_each_k_clique(graph, k, min_matching, predicate, |_| count += 1);
+    count
+}
+
+/// Grows `current` one node at a time using only higher-indexed neighbours, so every clique is
+/// built exactly once. `visit` is called for every partial clique and can return `false` to prune it.
+fn extend_k_clique(
+    forward_adjacency: &[BitSet],
+    k: usize,
+    current: &mut Vec<NodeIndex>,
+    candidates: &BitSet,
+    visit: &mut impl FnMut(&[NodeIndex]) -> bool,
+) {
+    for v in candidates.iter() {
+        current.push(NodeIndex::new(v));
+        if visit(current) && current.len() < k {
+            extend_k_clique(
+                forward_adjacency,
+                k,
+                current,
+                &candidates.intersection(&forward_adjacency[v]),
+                visit,
+            );
+        }
+        current.pop();
+    }
+}
+
+/// Lists every maximal clique in the graph using Bron-Kerbosch with pivoting.
+pub fn maximal_cliques<N, E>(graph: &UnGraph<N, E>) -> Vec<Vec<NodeIndex>> {
+    let node_count = graph.node_count();
+    let adjacency = adjacency(graph, false);
+
+    let mut all_nodes = BitSet::new(node_count);
+    (0..node_count).for_each(|i| all_nodes.insert(i));
+
+    let mut cliques = vec![];
+    bron_kerbosch(
+        &adjacency,
+        &mut vec![],
+        all_nodes,
+        BitSet::new(node_count),
+        &mut cliques,
+    );
+    cliques
+}
+
+/// Builds a neighbour set for every node. If `forward_only` is set, only neighbours with a higher
+/// index are included.
+fn adjacency<N, E>(graph: &UnGraph<N, E>, forward_only: bool) -> Vec<BitSet> {
+    graph
+        .node_indices()
+        .map(|n| {
+            let mut set = BitSet::new(graph.node_count());
+            graph
+                .neighbors_undirected(n)
+                .filter(|&neighbour| neighbour != n)
+                .filter(|&neighbour| !forward_only || neighbour > n)
+                .for_each(|neighbour| set.insert(neighbour.index()));
+            set
+        })
+        .collect()
+}
+
+fn bron_kerbosch(
+    adjacency: &[BitSet],
+    current: &mut Vec<NodeIndex>,
+    mut candidates: BitSet,
+    mut excluded: BitSet,
+    cliques: &mut Vec<Vec<NodeIndex>>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            cliques.push(current.clone());
+        }
+        return;
+    }
+
+    // pick the pivot that leaves the least amount of candidates to branch on
+    let pivot = candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|&u| candidates.intersection_len(&adjacency[u]))
+        .unwrap();
+
+    let branches = candidates
+        .iter()
+        .filter(|&v| !adjacency[pivot].contains(v))
+        .collect::<Vec<_>>();
+
+    for v in branches {
+        current.push(NodeIndex::new(v));
+        bron_kerbosch(
+            adjacency,
+            current,
+            candidates.intersection(&adjacency[v]),
+            excluded.intersection(&adjacency[v]),
+            cliques,
+        );
+        current.pop();
+
+        candidates.remove(v);
+        excluded.insert(v);
+    }
+}
+
+#[derive(Clone)]
+struct BitSet {
+    blocks: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        Self {
+            blocks: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.blocks[i / 64] |= 1 << (i % 64);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.blocks[i / 64] &= !(1 << (i % 64));
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.blocks[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn is_empty(&self) -> bool {
+        self.blocks.iter().all(|&b| b == 0)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        Self {
+            blocks: (self.blocks.iter().zip(&other.blocks))
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+
+    fn intersection_len(&self, other: &Self) -> u32 {
+        (self.blocks.iter().zip(&other.blocks))
+            .map(|(a, b)| (a & b).count_ones())
+            .sum()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter().enumerate().flat_map(|(i, &block)| {
+            let mut block = block;
+            std::iter::from_fn(move || {
+                if block == 0 {
+                    return None;
+                }
+                let bit = block.trailing_zeros() as usize;
+                block &= block - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
 #[test]
 fn test_silver_sample() {
     let output = Day::calculate_silver(Day::INPUT_SAMPLE);
@@ -161,3 +312,157 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!("hf,hz,lb,lm,ls,my,ps,qu,ra,uc,vi,xz,yv", output);
 }
+
+#[cfg(test)]
+fn generate_random_graph(node_count: usize, edge_chance: u64, seed: u64) -> UnGraph<(), ()> {
+    let mut rng = utils::XorShift::new(seed);
+
+    let mut graph = UnGraph::new_undirected();
+    let nodes = (0..node_count)
+        .map(|_| graph.add_node(()))
+        .collect::<Vec<_>>();
+    for i in 0..node_count {
+        for j in i + 1..node_count {
+            if rng.next_below(100) < edge_chance {
+                graph.add_edge(nodes[i], nodes[j], ());
+            }
+        }
+    }
+    graph
+}
+
+#[test]
+fn test_maximal_cliques_random() {
+    // brute force: check every subset of nodes for being a clique that can't be extended
+    fn brute_force(graph: &UnGraph<(), ()>) -> Vec<Vec<usize>> {
+        let node_count = graph.node_count();
+        let connected =
+            |a: usize, b: usize| graph.contains_edge(NodeIndex::new(a), NodeIndex::new(b));
+        let is_clique = |set: u32| {
+            (0..node_count).filter(|i| set & (1 << i) != 0).all(|i| {
+                (i + 1..node_count)
+                    .filter(|j| set & (1 << j) != 0)
+                    .all(|j| connected(i, j))
+            })
+        };
+
+        (1u32..1 << node_count)
+            .filter(|&set| is_clique(set))
+            .filter(|&set| {
+                (0..node_count)
+                    .filter(|i| set & (1 << i) == 0)
+                    .all(|i| !is_clique(set | (1 << i)))
+            })
+            .map(|set| (0..node_count).filter(|i| set & (1 << i) != 0).collect())
+            .collect()
+    }
+
+    for seed in 1..50 {
+        let node_count = 1 + seed as usize % 12;
+        let graph = generate_random_graph(node_count, 20 + seed % 70, seed);
+
+        let mut expected = brute_force(&graph);
+        expected.sort_unstable();
+
+        let mut actual = maximal_cliques(&graph)
+            .into_iter()
+            .map(|clique| {
+                let mut clique = clique.into_iter().map(|n| n.index()).collect::<Vec<_>>();
+                clique.sort_unstable();
+                clique
+            })
+            .collect::<Vec<_>>();
+        actual.sort_unstable();
+
+        assert_eq!(expected, actual, "seed {seed}");
+    }
+}
+
+#[test]
+fn test_maximal_cliques_high_degree() {
+    // every node has more neighbours than fit in a u32 bitmask
+    let graph = generate_random_graph(80, 100, 1);
+    let cliques = maximal_cliques(&graph);
+    assert_eq!(1, cliques.len());
+    assert_eq!(80, cliques[0].len());
+}
+
+#[test]
+fn test_k_cliques_sample() {
+    let (graph, node_lookup) = parse_graph(Day::INPUT_SAMPLE);
+    let name = |n: NodeIndex| String::from_utf8_lossy(&graph[n].to_ne_bytes()).to_string();
+
+    let mut triangles = 0;
+    for_each_k_clique(&graph, 3, 0, |_| true, |_| triangles += 1);
+    assert_eq!(12, triangles);
+    assert_eq!(12, count_k_cliques(&graph, 3, 0, |_| true));
+
+    let mut groups_of_four = vec![];
+    for_each_k_clique(
+        &graph,
+        4,
+        0,
+        |_| true,
+        |clique| {
+            let mut names = clique.iter().map(|&n| name(n)).collect::<Vec<_>>();
+            names.sort_unstable();
+            groups_of_four.push(names.join(","));
+        },
+    );
+    assert_eq!(vec!["co,de,ka,ta"], groups_of_four);
+
+    // the triangles with at least two computers starting with `t`, found from a named computer
+    let td = node_lookup[&pack_name("td")];
+    assert_eq!("td", name(td));
+    let mut with_two_t = vec![];
+    for_each_k_clique(
+        &graph,
+        3,
+        2,
+        |&n| starts_with_t(n),
+        |clique| {
+            if clique.contains(&td) {
+                let mut names = clique.iter().map(|&n| name(n)).collect::<Vec<_>>();
+                names.sort_unstable();
+                with_two_t.push(names.join(","));
+            }
+        },
+    );
+    assert_eq!(vec!["tc,td,wh"], with_two_t);
+    assert_eq!(1, count_k_cliques(&graph, 4, 1, |&n| starts_with_t(n)));
+    assert_eq!(0, count_k_cliques(&graph, 4, 2, |&n| starts_with_t(n)));
+}
+
+#[test]
+fn test_k_cliques_random() {
+    for seed in 1..30 {
+        let node_count = 1 + seed as usize % 12;
+        let graph = generate_random_graph(node_count, 20 + seed % 70, seed);
+        let connected =
+            |a: usize, b: usize| graph.contains_edge(NodeIndex::new(a), NodeIndex::new(b));
+
+        // the predicate matches all nodes with an even index
+        let graph = graph.map(|n, _| n.index() % 2 == 0, |_, _| ());
+
+        for k in 1..=5 {
+            // brute force: check every subset of k nodes
+            let expected = (0u32..1 << node_count)
+                .filter(|set| set.count_ones() as usize == k)
+                .filter(|&set| {
+                    (0..node_count).filter(|i| set & (1 << i) != 0).all(|i| {
+                        (i + 1..node_count)
+                            .filter(|j| set & (1 << j) != 0)
+                            .all(|j| connected(i, j))
+                    })
+                })
+                .filter(|&set| (set & 0x55555555).count_ones() >= 2)
+                .count();
+
+            assert_eq!(
+                expected,
+                count_k_cliques(&graph, k, 2, |&is_even| is_even),
+                "seed {seed}, k {k}"
+            );
+        }
+    }
+}
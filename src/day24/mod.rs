@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
-use petgraph::{visit::EdgeRef, Graph};
+use fnv::FnvBuildHasher;
+use petgraph::{graph::NodeIndex, visit::EdgeRef, Graph};
 
 use super::*;
 
@@ -12,54 +13,10 @@ impl SolutionSilver<usize> for Day {
     const INPUT_REAL: &'static str = include_str!("input_real.txt");
 
     fn calculate_silver(input: &str) -> usize {
-        let (part1, part2) = input.split_once("\n\n").unwrap();
-
-        let mut wire_states = HashMap::new();
-
-        part1.lines().for_each(|line| {
-            let (name, num) = line.split_once(": ").unwrap();
-            let num: usize = num.parse().unwrap();
-            wire_states.insert(name, num);
-        });
-
-        let mut any_changed = true;
-
-        while any_changed {
-            any_changed = false;
-
-            part2.lines().for_each(|line| {
-                let (expr, result) = line.split_once(" -> ").unwrap();
-                let (term1, expr) = expr.split_once(' ').unwrap();
-                let (op, term2) = expr.split_once(' ').unwrap();
+        let circuit = Circuit::parse(input);
+        let wire_states = circuit.evaluate();
 
-                if let (None, Some(val1), Some(val2)) = (
-                    wire_states.get(result),
-                    wire_states.get(term1),
-                    wire_states.get(term2),
-                ) {
-                    match op {
-                        "AND" => {
-                            wire_states.insert(result, val1 & val2);
-                        }
-                        "OR" => {
-                            wire_states.insert(result, val1 | val2);
-                        }
-                        "XOR" => {
-                            wire_states.insert(result, val1 ^ val2);
-                        }
-                        _ => todo!(),
-                    }
-
-                    any_changed = true;
-                }
-            });
-        }
-
-        wire_states
-            .into_iter()
-            .filter(|(k, _)| k.starts_with('z'))
-            .map(|(k, v)| v << k[1..].parse::<usize>().unwrap())
-            .sum()
+        circuit.output(&wire_states)
     }
 }
 
@@ -96,63 +53,211 @@ impl SolutionGold<usize, String> for Day {
             }
         }
 
+        let circuit = Circuit::parse(&input);
+        circuit
+            .verify_adder()
+            .expect("circuit should be a valid adder");
+
+        let mut swaps = swaps
+            .into_iter()
+            .flat_map(|(a, b)| [a, b])
+            .collect::<Vec<_>>();
+        swaps.sort_unstable();
+        swaps.join(",")
+    }
+}
+
+/// A dense id for a wire, as handed out by [`WireTable`].
+pub type WireId = u32;
+
+/// Interns wire names into dense ids so wires can be looked up in O(1) and stored in flat arrays.
+#[derive(Debug, Default)]
+pub struct WireTable<'a> {
+    ids: HashMap<&'a str, WireId, FnvBuildHasher>,
+    names: Vec<&'a str>,
+}
+
+impl<'a> WireTable<'a> {
+    /// Returns the id for the given name, allocating a new one if it wasn't seen before.
+    pub fn intern(&mut self, name: &'a str) -> WireId {
+        *self.ids.entry(name).or_insert_with(|| {
+            self.names.push(name);
+            (self.names.len() - 1) as WireId
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: WireId) -> &'a str {
+        self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Gate {
+    op: Operation,
+    term1: WireId,
+    term2: WireId,
+    result: WireId,
+}
+
+/// A parsed puzzle input, with all wires interned in a shared [`WireTable`].
+#[derive(Debug)]
+pub struct Circuit<'a> {
+    wires: WireTable<'a>,
+    initial_states: Vec<(WireId, bool)>,
+    gates: Vec<Gate>,
+}
+
+impl<'a> Circuit<'a> {
+    pub fn parse(input: &'a str) -> Self {
         let (part1, part2) = input.split_once("\n\n").unwrap();
 
-        let mut graph = Graph::new();
-
-        part1.lines().for_each(|line| {
-            let (name, num) = line.split_once(": ").unwrap();
-            let num: usize = num.parse().unwrap();
-
-            let val_wt = graph.add_node(name.to_string());
-            let num_wt = graph.add_node(num.to_string());
-
-            graph.add_edge(num_wt, val_wt, Operation::Assign);
-        });
-
-        part2.lines().for_each(|line| {
-            let (expr, result) = line.split_once(" -> ").unwrap();
-            let (term1, expr) = expr.split_once(' ').unwrap();
-            let (op, term2) = expr.split_once(' ').unwrap();
-
-            let term1_wt = graph
-                .node_indices()
-                .find(|&idx| graph.node_weight(idx).unwrap() == term1)
-                .unwrap_or_else(|| graph.add_node(term1.to_string()));
-            let term2_wt = graph
-                .node_indices()
-                .find(|&idx| graph.node_weight(idx).unwrap() == term2)
-                .unwrap_or_else(|| graph.add_node(term2.to_string()));
-            let result_wt = graph
-                .node_indices()
-                .find(|&idx| graph.node_weight(idx).unwrap() == result)
-                .unwrap_or_else(|| graph.add_node(result.to_string()));
-
-            let op = match op {
-                "AND" => Operation::And,
-                "OR" => Operation::Or,
-                "XOR" => Operation::Xor,
-                _ => unreachable!(),
+        let mut wires = WireTable::default();
+
+        let initial_states = part1
+            .lines()
+            .map(|line| {
+                let (name, num) = line.split_once(": ").unwrap();
+                (wires.intern(name), num == "1")
+            })
+            .collect();
+
+        let gates = part2
+            .lines()
+            .map(|line| {
+                let (expr, result) = line.split_once(" -> ").unwrap();
+                let (term1, expr) = expr.split_once(' ').unwrap();
+                let (op, term2) = expr.split_once(' ').unwrap();
+
+                let op = match op {
+                    "AND" => Operation::And,
+                    "OR" => Operation::Or,
+                    "XOR" => Operation::Xor,
+                    _ => unreachable!(),
+                };
+
+                Gate {
+                    op,
+                    term1: wires.intern(term1),
+                    term2: wires.intern(term2),
+                    result: wires.intern(result),
+                }
+            })
+            .collect();
+
+        Self {
+            wires,
+            initial_states,
+            gates,
+        }
+    }
+
+    pub fn wires(&self) -> &WireTable<'a> {
+        &self.wires
+    }
+
+    /// Evaluates all gates, returning the state of every wire indexed by its [`WireId`]. Wires that
+    /// never receive a signal are `None`.
+    pub fn evaluate(&self) -> Vec<Option<bool>> {
+        let mut wire_states = vec![None; self.wires.len()];
+        for &(wire, state) in &self.initial_states {
+            wire_states[wire as usize] = Some(state);
+        }
+
+        // process gates in topological order: a gate becomes ready once both its inputs are known
+        let mut gates_per_wire = vec![vec![]; self.wires.len()];
+        let mut missing_inputs = vec![0u8; self.gates.len()];
+        for (i, gate) in self.gates.iter().enumerate() {
+            for term in [gate.term1, gate.term2] {
+                if wire_states[term as usize].is_none() {
+                    gates_per_wire[term as usize].push(i);
+                    missing_inputs[i] += 1;
+                }
+            }
+        }
+
+        let mut ready = (0..self.gates.len())
+            .filter(|&i| missing_inputs[i] == 0)
+            .collect::<Vec<_>>();
+
+        while let Some(i) = ready.pop() {
+            let gate = self.gates[i];
+            let (Some(val1), Some(val2)) = (
+                wire_states[gate.term1 as usize],
+                wire_states[gate.term2 as usize],
+            ) else {
+                unreachable!("gate inputs should be known");
             };
 
-            graph.add_edge(term1_wt, result_wt, op);
-            graph.add_edge(term2_wt, result_wt, op);
-        });
+            if wire_states[gate.result as usize].is_some() {
+                continue;
+            }
+
+            wire_states[gate.result as usize] = Some(match gate.op {
+                Operation::And => val1 & val2,
+                Operation::Or => val1 | val2,
+                Operation::Xor => val1 ^ val2,
+            });
+
+            for &dependent in &gates_per_wire[gate.result as usize] {
+                missing_inputs[dependent] -= 1;
+                if missing_inputs[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        wire_states
+    }
+
+    /// Combines the states of all `z` wires into a number.
+    pub fn output(&self, wire_states: &[Option<bool>]) -> usize {
+        wire_states
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| **v == Some(true))
+            .map(|(id, _)| self.wires.name(id as WireId))
+            .filter(|k| k.starts_with('z'))
+            .map(|k| 1 << k[1..].parse::<usize>().unwrap())
+            .sum()
+    }
+
+    /// Checks that the circuit is a ripple-carry adder of its `x` and `y` inputs, returning the bit
+    /// at which it first deviates otherwise.
+    pub fn verify_adder(&self) -> Result<(), usize> {
+        // node indices are the same as the wire ids
+        let mut graph =
+            Graph::<(), Operation>::with_capacity(self.wires.len(), self.gates.len() * 2);
+        for _ in 0..self.wires.len() {
+            graph.add_node(());
+        }
+
+        for gate in &self.gates {
+            let result = NodeIndex::new(gate.result as usize);
+            graph.add_edge(NodeIndex::new(gate.term1 as usize), result, gate.op);
+            graph.add_edge(NodeIndex::new(gate.term2 as usize), result, gate.op);
+        }
 
-        // println!("{:?}", Dot::with_config(&graph, &[]));
+        let input_bits = (0..)
+            .take_while(|i| self.wires.get(&format!("x{:02}", i)).is_some())
+            .count();
 
         // we need to fix a graph that implements binary addition 0_o
         let mut previous_carry = None;
-        for i in 0..=44 {
-            let x = graph
-                .node_indices()
-                .find(|&idx| graph.node_weight(idx).unwrap() == &format!("x{:02}", i))
-                .unwrap();
-            let y = graph
-                .node_indices()
-                .find(|&idx| graph.node_weight(idx).unwrap() == &format!("y{:02}", i))
-                .unwrap();
-
+        for i in 0..input_bits {
+            let x = NodeIndex::new(self.wires.get(&format!("x{:02}", i)).ok_or(i)? as usize);
+            let y = NodeIndex::new(self.wires.get(&format!("y{:02}", i)).ok_or(i)? as usize);
             let half_adder_sum = single(
                 intersect(
                     graph
@@ -166,7 +271,7 @@ impl SolutionGold<usize, String> for Day {
                 )
                 .into_iter(),
             )
-            .unwrap();
+            .ok_or(i)?;
 
             let half_adder_carry = single(
                 intersect(
@@ -181,13 +286,13 @@ impl SolutionGold<usize, String> for Day {
                 )
                 .into_iter(),
             )
-            .unwrap();
+            .ok_or(i)?;
 
             /*
             println!(
                 "half_adder_sum: {}, half_adder_carry: {}",
-                graph.node_weight(half_adder_sum).unwrap(),
-                graph.node_weight(half_adder_carry).unwrap(),
+                self.wires.name(half_adder_sum.index() as WireId),
+                self.wires.name(half_adder_carry.index() as WireId),
             );
             */
 
@@ -205,7 +310,7 @@ impl SolutionGold<usize, String> for Day {
                     )
                     .into_iter(),
                 )
-                .expect("find sum");
+                .ok_or(i)?;
 
                 let intermediate = single(
                     intersect(
@@ -220,7 +325,7 @@ impl SolutionGold<usize, String> for Day {
                     )
                     .into_iter(),
                 )
-                .expect("find intermediate");
+                .ok_or(i)?;
 
                 let new_carry = single(
                     intersect(
@@ -235,14 +340,14 @@ impl SolutionGold<usize, String> for Day {
                     )
                     .into_iter(),
                 )
-                .expect("find new carry");
+                .ok_or(i)?;
 
                 /*
                 println!(
                     "sum: {}, intermediate: {}, new_carry: {}",
-                    graph.node_weight(sum).unwrap(),
-                    graph.node_weight(intermediate).unwrap(),
-                    graph.node_weight(new_carry).unwrap(),
+                    self.wires.name(sum.index() as WireId),
+                    self.wires.name(intermediate.index() as WireId),
+                    self.wires.name(new_carry.index() as WireId),
                 );
                 */
 
@@ -252,18 +357,59 @@ impl SolutionGold<usize, String> for Day {
             }
         }
 
-        let mut swaps = swaps
-            .into_iter()
-            .flat_map(|(a, b)| [a, b])
-            .collect::<Vec<_>>();
-        swaps.sort_unstable();
-        swaps.join(",")
+        Ok(())
     }
 }
 
+/// Generates a puzzle input describing a correct ripple-carry adder with the given amount of input
+/// bits, with random input values and gates in a shuffled order.
+pub fn generate_adder(bits: usize, seed: u64) -> String {
+    use std::fmt::Write;
+
+    let mut rng = utils::XorShift::new(seed);
+
+    let mut output = String::new();
+    for wire in ['x', 'y'] {
+        for i in 0..bits {
+            writeln!(output, "{wire}{i:02}: {}", rng.next_below(2)).unwrap();
+        }
+    }
+    output.push('\n');
+
+    let mut gates = vec![];
+    for i in 0..bits {
+        if i == 0 {
+            gates.push("x00 XOR y00 -> z00".to_string());
+            gates.push("x00 AND y00 -> c00".to_string());
+            continue;
+        }
+
+        let carry_out = if i == bits - 1 {
+            format!("z{bits:02}")
+        } else {
+            format!("c{i:02}")
+        };
+        gates.push(format!("x{i:02} XOR y{i:02} -> s{i:02}"));
+        gates.push(format!("y{i:02} AND x{i:02} -> a{i:02}"));
+        gates.push(format!("s{i:02} XOR c{:02} -> z{i:02}", i - 1));
+        gates.push(format!("c{:02} AND s{i:02} -> t{i:02}", i - 1));
+        gates.push(format!("a{i:02} OR t{i:02} -> {carry_out}"));
+    }
+
+    for i in (1..gates.len()).rev() {
+        gates.swap(i, rng.next_below(i as u64 + 1) as usize);
+    }
+
+    for gate in gates {
+        output.push_str(&gate);
+        output.push('\n');
+    }
+
+    output
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum Operation {
-    Assign,
     And,
     Or,
     Xor,
@@ -312,3 +458,27 @@ fn test_gold_real() {
     let output = Day::calculate_gold(Day::INPUT_REAL);
     assert_eq!("cvp,mkk,qbw,wcb,wjb,z10,z14,z34", output);
 }
+
+#[test]
+fn test_generated_adder() {
+    for bits in [2, 10, 45, 63] {
+        let input = generate_adder(bits, bits as u64);
+        let circuit = Circuit::parse(&input);
+        assert_eq!(Ok(()), circuit.verify_adder());
+
+        let input_value = |wire: &str| {
+            input
+                .lines()
+                .take_while(|line| !line.is_empty())
+                .filter(|line| line.starts_with(wire))
+                .map(|line| line.split_once(": ").unwrap())
+                .map(|(name, num)| {
+                    num.parse::<usize>().unwrap() << name[1..].parse::<usize>().unwrap()
+                })
+                .sum::<usize>()
+        };
+
+        let expected = input_value("x") + input_value("y");
+        assert_eq!(expected, circuit.output(&circuit.evaluate()));
+    }
+}
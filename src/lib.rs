@@ -12,6 +12,7 @@
 
 use std::fmt::Display;
 
+mod math;
 mod utils;
 
 macro_rules! register_days {
@@ -69,36 +70,65 @@ where
     (ret, elapsed)
 }
 
+fn print_explanation<F>(explain: F)
+where
+    F: FnOnce() -> Option<String>,
+{
+    if cfg!(feature = "explain") {
+        if let Some(explanation) = explain() {
+            println!("{explanation}");
+        }
+    }
+}
+
 pub trait SolutionSilver<TSilver: Display> {
     const DAY: u32;
     const INPUT_SAMPLE: &'static str;
     const INPUT_REAL: &'static str;
 
     fn execute_silver() {
-        let (output, time) = run_timed(|| Self::calculate_silver(Self::INPUT_SAMPLE));
+        let (output, time) = run_timed(|| Self::calculate_silver_sample(Self::INPUT_SAMPLE));
         println!("Day {:02}, silver (s) | {time:>11?} | {output}", Self::DAY);
+        print_explanation(|| Self::explain_silver(Self::INPUT_SAMPLE));
         let (output, time) = run_timed(|| Self::calculate_silver(Self::INPUT_REAL));
         println!("Day {:02}, silver     | {time:>11?} | {output}", Self::DAY);
+        print_explanation(|| Self::explain_silver(Self::INPUT_REAL));
 
         println!("Day {:02} has no gold implementation", Self::DAY);
     }
 
     fn calculate_silver(input: &str) -> TSilver;
+
+    /// Solves silver for the sample input, for days where the sample uses different parameters
+    /// than the real puzzle, such as a smaller grid.
+    fn calculate_silver_sample(input: &str) -> TSilver {
+        Self::calculate_silver(input)
+    }
+
+    /// Returns a breakdown of how the silver answer is made up, which the runner prints when the
+    /// `explain` feature is enabled.
+    fn explain_silver(_input: &str) -> Option<String> {
+        None
+    }
 }
 
 pub trait SolutionGold<TSilver: Display, TGold: Display>: SolutionSilver<TSilver> {
     const INPUT_SAMPLE_GOLD: &'static str = Self::INPUT_SAMPLE;
 
     fn execute() {
-        let (output, time) = run_timed(|| Self::calculate_silver(Self::INPUT_SAMPLE));
+        let (output, time) = run_timed(|| Self::calculate_silver_sample(Self::INPUT_SAMPLE));
         println!("Day {:02}, silver (s) | {time:>10?} | {output}", Self::DAY);
+        print_explanation(|| Self::explain_silver(Self::INPUT_SAMPLE));
         let (output, time) = run_timed(|| Self::calculate_silver(Self::INPUT_REAL));
         println!("Day {:02}, silver     | {time:>10?} | {output}", Self::DAY);
+        print_explanation(|| Self::explain_silver(Self::INPUT_REAL));
 
-        let (output, time) = run_timed(|| Self::calculate_gold(Self::INPUT_SAMPLE_GOLD));
+        let (output, time) = run_timed(|| Self::calculate_gold_sample(Self::INPUT_SAMPLE_GOLD));
         println!("Day {:02}, gold (s)   | {time:>10?} | {output}", Self::DAY);
+        print_explanation(|| Self::explain_gold(Self::INPUT_SAMPLE_GOLD));
         let (output, time) = run_timed(|| Self::calculate_gold(Self::INPUT_REAL));
         println!("Day {:02}, gold       | {time:>10?} | {output}", Self::DAY);
+        print_explanation(|| Self::explain_gold(Self::INPUT_REAL));
 
         #[cfg(feature = "profile")]
         for _ in 0..100 {
@@ -107,4 +137,16 @@ pub trait SolutionGold<TSilver: Display, TGold: Display>: SolutionSilver<TSilver
     }
 
     fn calculate_gold(input: &str) -> TGold;
+
+    /// Solves gold for the sample input, for days where the sample uses different parameters than
+    /// the real puzzle, such as a smaller grid.
+    fn calculate_gold_sample(input: &str) -> TGold {
+        Self::calculate_gold(input)
+    }
+
+    /// Returns a breakdown of how the gold answer is made up, which the runner prints when the
+    /// `explain` feature is enabled.
+    fn explain_gold(_input: &str) -> Option<String> {
+        None
+    }
 }
@@ -0,0 +1,130 @@
+#![allow(unused)]
+
+/// Calculates the least common multiple of a list of numbers.
+///
+/// https://github.com/TheAlgorithms/Rust/blob/master/src/math/lcm_of_n_numbers.rs
+pub fn lcm(nums: &[usize]) -> usize {
+    if nums.len() == 1 {
+        return nums[0];
+    }
+    let a = nums[0];
+    let b = lcm(&nums[1..]);
+    a * b / gcd(a, b)
+}
+
+/// Calculates the greatest common divisor of two numbers.
+///
+/// https://github.com/TheAlgorithms/Rust/blob/master/src/math/lcm_of_n_numbers.rs
+pub fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        return a;
+    }
+    gcd(b, a % b)
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the non-negative greatest
+/// common divisor of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Finds the smallest non-negative `x` where `x % m1 == r1` and `x % m2 == r2`, using the Chinese
+/// remainder theorem. The moduli must be coprime.
+pub fn chinese_remainder((r1, m1): (i128, i128), (r2, m2): (i128, i128)) -> Option<i128> {
+    let (g, inverse, _) = extended_gcd(m1, m2);
+    if g != 1 {
+        return None;
+    }
+
+    // x = r1 + m1 * k, where m1 * k == r2 - r1 (mod m2)
+    let k = ((r2 - r1) * inverse).rem_euclid(m2);
+    Some((r1 + m1 * k).rem_euclid(m1 * m2))
+}
+
+/// Integer division that rounds towards negative infinity.
+pub fn div_floor(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if a % b != 0 && ((a < 0) != (b < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+/// Integer division that rounds towards positive infinity.
+pub fn div_ceil(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if a % b != 0 && ((a < 0) == (b < 0)) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(&[4, 6]), 12);
+        assert_eq!(lcm(&[101, 103]), 10403);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [
+            (240, 46),
+            (46, 240),
+            (-240, 46),
+            (240, -46),
+            (0, 5),
+            (5, 0),
+            (7, 7),
+        ] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(
+                g,
+                gcd(a.unsigned_abs() as usize, b.unsigned_abs() as usize) as i128
+            );
+            assert_eq!(a * x + b * y, g, "{a}, {b}");
+        }
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(chinese_remainder((2, 3), (3, 5)), Some(8));
+        assert_eq!(chinese_remainder((0, 101), (0, 103)), Some(0));
+        assert_eq!(chinese_remainder((13, 101), (7, 103)), Some(316));
+        assert_eq!(chinese_remainder((1, 4), (1, 6)), None);
+    }
+
+    #[test]
+    fn test_div_rounding() {
+        assert_eq!(div_floor(7, 2), 3);
+        assert_eq!(div_floor(-7, 2), -4);
+        assert_eq!(div_floor(7, -2), -4);
+        assert_eq!(div_floor(-6, 2), -3);
+        assert_eq!(div_ceil(7, 2), 4);
+        assert_eq!(div_ceil(-7, 2), -3);
+        assert_eq!(div_ceil(-7, -2), 4);
+        assert_eq!(div_ceil(6, 2), 3);
+    }
+}
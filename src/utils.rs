@@ -55,6 +55,28 @@ pub fn split_once_2(haystack: &[u8], delimiter: u8) -> (&[u8], &[u8]) {
     }
 }
 
+/// A tiny xorshift PRNG, used to generate inputs for tests and benchmarks.
+pub struct XorShift(u64);
+
+impl XorShift {
+    pub fn new(seed: u64) -> Self {
+        // the state must never be 0
+        Self(seed | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a number in `0..max`. This is slightly biased, which is fine for our purposes.
+    pub fn next_below(&mut self, max: u64) -> u64 {
+        self.next_u64() % max
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;